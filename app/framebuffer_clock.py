@@ -7,18 +7,26 @@ No X11, no pygame, no Rust - pure PIL with pre-converted sprite cache.
 import os
 import sys
 import logging
+import argparse
 import socket
 import subprocess
 import time
+import signal
 import select
 import termios
 import tty
 import mmap
 import math
-from datetime import datetime
+import colorsys
+import fcntl
+import struct
+import re
+import json
+from datetime import datetime, timedelta
 from pathlib import Path
 import yaml
-from PIL import Image, ImageDraw, ImageFont
+from collections import OrderedDict
+from PIL import Image, ImageDraw, ImageFilter, ImageFont, ImageOps
 import numpy as np
 from typing import Optional
 
@@ -30,65 +38,285 @@ except Exception:
     ecodes = None
     list_devices = lambda: []
 
+# Supported framebuffer pixel formats -> bytes per pixel
+PIXEL_FORMATS = {
+    'RGB565': 2,
+    'XRGB8888': 4,
+    'ARGB8888': 4,
+    'RGBA8888': 4,
+}
+
+# Default (offset, length) of the R, G, B bitfields within a native pixel word
+PIXEL_FORMAT_CHANNELS = {
+    'RGB565': ((11, 5), (5, 6), (0, 5)),
+    'XRGB8888': ((16, 8), (8, 8), (0, 8)),
+    'ARGB8888': ((16, 8), (8, 8), (0, 8)),
+    'RGBA8888': ((24, 8), (16, 8), (8, 8)),
+}
+
+# Default (offset, length) of the alpha bitfield; written fully opaque so compositing
+# drivers/overlays don't treat the clock as transparent
+PIXEL_FORMAT_ALPHA = {
+    'ARGB8888': (24, 8),
+    'RGBA8888': (0, 8),
+}
+
+# Upper bound on lazily rendered date sprites (LRU) so unusual date formats can't grow memory forever
+DATE_SPRITE_CACHE_MAX = 256
+
+# Time sprite sets kept per font size (LRU); burn-in variation picks from this many sizes so it reuses them
+TIME_SPRITE_SIZES_MAX = 5
+
+# TIME_STYLE=analog: face diameter as a fraction of the screen height (or width, if smaller)
+ANALOG_FACE_FRACTION = 0.6
+
+# Lines read from EXTRA_LINES_FILE beyond this many are ignored (they would run off the screen anyway)
+EXTRA_LINES_MAX = 8
+
+# Seven-segment layout for TIME_STYLE=segment: segments lit per digit (a=top, clockwise, g=middle)
+SEGMENT_DIGITS = {
+    '0': 'abcdef', '1': 'bc', '2': 'abdeg', '3': 'abcdg', '4': 'bcfg',
+    '5': 'acdfg', '6': 'acdefg', '7': 'abc', '8': 'abcdefg', '9': 'abcdfg',
+    '-': 'g',
+}
+
+# linux/fb.h ioctl request numbers
+FBIOGET_VSCREENINFO = 0x4600
+FBIOGET_FSCREENINFO = 0x4602
+FB_VAR_SCREENINFO_SIZE = 160  # struct fb_var_screeninfo: 40 x __u32
+# struct fb_fix_screeninfo uses native longs, so let struct apply the platform's alignment
+FB_FIX_SCREENINFO_FORMAT = '@16sLIIIIHHHILIIH2H0L'
+
+# Backoff between framebuffer reopen attempts after write errors (seconds, doubles up to the cap)
+FB_REOPEN_INITIAL_DELAY = 0.25
+FB_REOPEN_MAX_DELAY = 5.0
+
+# Process exit codes (start.sh restarts on any non-zero code; these tell the failures apart in logs)
+EXIT_CONFIG_ERROR = 2
+EXIT_INIT_ERROR = 3
+
+# Basic named colors accepted wherever a hex color is configured
+NAMED_COLORS = {
+    'red': (255, 0, 0),
+    'green': (0, 255, 0),
+    'blue': (0, 0, 255),
+    'white': (255, 255, 255),
+    'black': (0, 0, 0),
+    'yellow': (255, 255, 0),
+    'cyan': (0, 255, 255),
+    'magenta': (255, 0, 255),
+}
+
+
+def _parse_unit(component: str) -> float:
+    """Parse a saturation/value/lightness component: '0.5' or '50%' -> 0.5."""
+    component = component.strip()
+    if component.endswith('%'):
+        return float(component[:-1]) / 100.0
+    return float(component)
+
+
+def kelvin_to_rgb(kelvin: float) -> tuple:
+    """Approximate blackbody color (0-255 per channel) for a color temperature (Tanner Helland fit).
+    6500K is close to white; lower values shift towards amber.
+    """
+    temp = max(1000.0, min(40000.0, float(kelvin))) / 100.0
+    if temp <= 66:
+        r = 255.0
+        g = 99.4708025861 * math.log(temp) - 161.1195681661
+    else:
+        r = 329.698727446 * (temp - 60) ** -0.1332047592
+        g = 288.1221695283 * (temp - 60) ** -0.0755148492
+    if temp >= 66:
+        b = 255.0
+    elif temp <= 19:
+        b = 0.0
+    else:
+        b = 138.5177312231 * math.log(temp - 10) - 305.0447927307
+    return tuple(int(round(max(0.0, min(255.0, c)))) for c in (r, g, b))
+
+
+def parse_color(value: str) -> tuple:
+    """Parse '#RRGGBB', '#RGB', '#RRGGBBAA', 'hsv(h,s,v)', 'hsl(h,s,l)' or a named color
+    into an (r, g, b) tuple. Hue is in degrees, the other components 0-1 or percentages.
+    Alpha in 8-digit hex is ignored. Raises ValueError for anything else.
+    """
+    text = str(value).strip().lower()
+    if text in NAMED_COLORS:
+        return NAMED_COLORS[text]
+    if text.startswith(('hsv(', 'hsl(')) and text.endswith(')'):
+        parts = text[4:-1].split(',')
+        if len(parts) != 3:
+            raise ValueError(f"expected 3 components in {value!r}")
+        try:
+            h = (float(parts[0]) % 360.0) / 360.0
+            a, b = (min(1.0, max(0.0, _parse_unit(p))) for p in parts[1:])
+        except ValueError:
+            raise ValueError(f"invalid color components in {value!r}") from None
+        if text.startswith('hsv('):
+            rgb = colorsys.hsv_to_rgb(h, a, b)
+        else:
+            rgb = colorsys.hls_to_rgb(h, b, a)
+        return tuple(int(round(c * 255)) for c in rgb)
+    digits = text[1:] if text.startswith('#') else text
+    if len(digits) == 3:
+        digits = ''.join(c * 2 for c in digits)
+    if len(digits) not in (6, 8):
+        raise ValueError(f"unsupported color {value!r}")
+    try:
+        return tuple(int(digits[i:i+2], 16) for i in (0, 2, 4))
+    except ValueError:
+        raise ValueError(f"invalid hex color {value!r}") from None
+
+
+def setting_number(name: str, config: dict, key: str, default, cast=int, minimum=None, maximum=None):
+    """Read a numeric setting from env var name, then config[key], then default.
+    Unparseable values from either source log a warning naming the variable or config key
+    and fall back to the next source; out-of-range values are clamped with a warning.
+    """
+    value, source = default, name
+    raw = config.get(key)
+    if raw is not None and raw != '':
+        try:
+            value, source = cast(raw), f"config {key}"
+        except (TypeError, ValueError):
+            logging.warning(f"Invalid config {key}: {raw!r} (expected {cast.__name__}), using {default}")
+    raw = os.environ.get(name, '').strip()
+    if raw:
+        try:
+            value, source = cast(raw), name
+        except ValueError:
+            logging.warning(f"Invalid {name}={raw!r} (expected {cast.__name__}), using {value}")
+    clamped = value
+    if minimum is not None:
+        clamped = max(minimum, clamped)
+    if maximum is not None:
+        clamped = min(maximum, clamped)
+    if clamped != value:
+        logging.warning(f"{source}={value} out of range [{minimum}, {maximum}], using {clamped}")
+    return clamped
+
+
 # Lazy imports for optional features (loaded only when enabled)
 WeatherService = None
 RTCManager = None
+light_sensor = None
 
 
 class FramebufferClock:
     """Direct framebuffer digital clock display."""
     
-    def __init__(self, config: dict, build_info: Optional[dict] = None):
-        """Initialize framebuffer clock."""
+    def __init__(self, config: dict, build_info: Optional[dict] = None,
+                 fb_device: Optional[str] = None, fb_size: Optional[tuple] = None):
+        """Initialize framebuffer clock.
+        fb_device overrides the FRAMEBUFFER env var; fb_size skips the size query (tests, headless runs).
+        """
         self.config = config
         self.running = True
+        self.reload_requested = False
+        self.status_requested = False
+        self.snapshot_requested = False
+        self._wake_fds = None  # Self-pipe cutting the main loop's sleep short (SIGTERM/SIGINT, SIGHUP, SIGUSR1/SIGUSR2)
+        # `kill -USR1` writes a one-line JSON snapshot of the clock state here (logs go to stdout)
+        self.status_file = os.environ.get('STATUS_FILE') or config.get('display', {}).get('status_file', '/tmp/clock_status.json')
+        # `kill -USR2` saves what the panel shows as a binary PPM here (debugging headless Pis)
+        self.snapshot_file = os.environ.get('SNAPSHOT_FILE') or config.get('display', {}).get('snapshot_file', '/tmp/clock_snapshot.ppm')
+        self.frames_rendered = 0
+        self._shown_texts = ('', '')  # (time, date) strings of the last render
         self.build_info = build_info or {}
         
         # Open framebuffer device
-        self.fb_device = os.environ.get('FRAMEBUFFER', '/dev/fb0')
+        self.fb_device = fb_device or os.environ.get('FRAMEBUFFER', '/dev/fb0')
+        self._fb_size_override = fb_size
         logging.info(f"Opening framebuffer device: {self.fb_device}")
+        # Headless backend for development: FRAMEBUFFER=png:/tmp/frames writes numbered PNGs
+        self._png_dir = None
+        self._png_frame = 0
+        if self.fb_device.startswith('png:'):
+            self._png_dir = self.fb_device[len('png:'):] or '.'
+            os.makedirs(self._png_dir, exist_ok=True)
+            logging.info(f"PNG backend: writing frames to {self._png_dir}")
+        elif not os.path.exists(self.fb_device):
+            logging.error(f"Framebuffer device {self.fb_device} not found (is the display connected and "
+                          f"the device passed to the container?); will keep retrying")
+        
+        # Query the kernel for the variable screen info once (None if ioctl unavailable)
+        self._fb_var = self.get_var_screeninfo()
         
         # Get framebuffer info
-        self.fb_width, self.fb_height = self.get_framebuffer_size()
-        logging.info(f"Framebuffer resolution: {self.fb_width}x{self.fb_height}")
-        
-        # Determine framebuffer pixel format once
-        self.fb_bpp = self.get_bits_per_pixel()
-        # Shadow framebuffer buffer (RGB565) for partial updates
-        if self.fb_bpp != 16:
-            logging.warning("Optimized blitter assumes 16bpp RGB565; other bpp will fallback to full-frame writes")
-        self.fb_shadow = np.zeros((self.fb_height, self.fb_width), dtype='<u2')
+        self._fb_phys_width, self._fb_phys_height = self.get_framebuffer_size()
+        logging.info(f"Framebuffer resolution: {self._fb_phys_width}x{self._fb_phys_height}")
+        
+        # Output rotation (clockwise). fb_width/fb_height are the logical canvas everything
+        # draws into; 90/270 swap them and writes map rects to physical pixels.
+        self.rotation = self.get_rotation(config.get('display', {}))
+        self.fb_width, self.fb_height = self._logical_size(self._fb_phys_width, self._fb_phys_height)
+        if self.rotation:
+            logging.info(f"Rotating output {self.rotation}° (logical canvas {self.fb_width}x{self.fb_height})")
+        
+        # Determine framebuffer pixel format (FB_FORMAT env overrides ioctl/sysfs)
+        self._detect_pixel_format()
+        # Row stride: kernel line_length (drivers may pad scanlines), else width * bytes-per-pixel
+        self._fb_fix = self.get_fix_screeninfo()
+        self._fb_stride_bytes = self.get_stride_bytes()
+        # Shadow framebuffer buffer (native pixel format) for partial updates
+        self.fb_shadow = np.full((self.fb_height, self.fb_width), self._fb_alpha_mask, dtype=self._shadow_dtype())
         # Track last drawn rects for clearing
         self._last_time_rect = None
         self._last_date_rect = None
         self._last_status_rect = None
         # Track dirty rectangles for partial framebuffer writes
         self._dirty_rects = []
+        # Full shadow copies only happen on the first frame or after an explicit clear
+        self._full_redraw = True
+        self._screensaver_blanked = False
+        # Total bytes copied into the framebuffer (inspect to catch full-screen copy regressions)
+        self.fb_bytes_written = 0
         # Try to memory-map framebuffer for fast partial writes
-        self.fb_mmap = None
-        try:
-            if self.fb_bpp == 16:
-                fb_size = self.fb_width * self.fb_height * 2
-                fb = open(self.fb_device, 'r+b', buffering=0)
-                self.fb_mmap = mmap.mmap(fb.fileno(), fb_size, access=mmap.ACCESS_WRITE)
-                self._fb_stride_bytes = self.fb_width * 2
-                self._fb_file = fb  # keep file open for mapping lifetime
-                logging.info("/dev/fb0 memory-mapped for fast partial updates")
-        except Exception as e:
-            self.fb_mmap = None
-            self._fb_file = None
-            logging.warning(f"Framebuffer mmap not available, falling back to writes: {e}")
+        self._map_framebuffer()
+        # Reopen backoff state (device can vanish on HDMI unplug/replug)
+        self._fb_reopen_delay = FB_REOPEN_INITIAL_DELAY
+        self._fb_reopen_at = 0.0
 
         # Log framebuffer pixel format
-        logging.info(f"Framebuffer bits-per-pixel: {self.fb_bpp}")
+        logging.info(f"Framebuffer bits-per-pixel: {self.fb_bpp} (format: {self.fb_format or 'unsupported'})")
         # Load configuration
         display_config = config.get('display', {})
-        self.color = self.hex_to_rgb(display_config.get('color', '#00FF00'))
-        self.bg_color = (0, 0, 0)  # Black background
+        self.color = self.hex_to_rgb(os.environ.get('DISPLAY_COLOR') or display_config.get('color', '#00FF00'),
+                                     name='DISPLAY_COLOR')
+        # Date line color - env var first, then config, defaulting to the main color
+        date_color = os.environ.get('DATE_COLOR') or display_config.get('date_color')
+        self.date_color = self.hex_to_rgb(date_color, fallback=self.color, name='DATE_COLOR') if date_color else self.color
+        # Background color - env var first, then config, defaulting to black
+        bg_color = os.environ.get('BG_COLOR') or display_config.get('background_color')
+        self.bg_color = self.hex_to_rgb(bg_color, fallback=(0, 0, 0), name='BG_COLOR') if bg_color else (0, 0, 0)
+        # Native pixel value used for clears (screensaver and exit blanking stay pure black)
+        self._bg_native = self._native_color(self.bg_color)
+        # Optional background image (scaled to fill the screen), used instead of bg_color for clears
+        self._bg_image_path = os.environ.get('BG_IMAGE') or display_config.get('background_image')
+        self._bg_image = self.load_background_image(self._bg_image_path)
+        # Drop shadow baked into the time/date sprites: SHADOW_OFFSET "dx,dy" in px (unset = off)
+        self.shadow_offset = self._parse_offset(os.environ.get('SHADOW_OFFSET') or display_config.get('shadow_offset'), 'SHADOW_OFFSET')
+        self.shadow_color = self.hex_to_rgb(os.environ.get('SHADOW_COLOR') or display_config.get('shadow_color', '#000000'),
+                                            fallback=(0, 0, 0), name='SHADOW_COLOR')
+        # Outline (stroke) around the time/date glyphs for readability on busy backgrounds (0 = off)
+        self.outline_width = setting_number('OUTLINE_WIDTH', display_config, 'outline_width', 0, minimum=0, maximum=20)
+        self.outline_color = self.hex_to_rgb(os.environ.get('OUTLINE_COLOR') or display_config.get('outline_color', '#000000'),
+                                             fallback=(0, 0, 0), name='OUTLINE_COLOR')
+        # GRADIENT "top,bottom": fill the time/date glyphs with a vertical gradient instead of a flat color
+        self.gradient = self._parse_gradient(os.environ.get('GRADIENT') or display_config.get('gradient'))
+        # RAINBOW: give each character its own fixed hue (GRADIENT wins if both are set)
+        rainbow_env = os.environ.get('RAINBOW', '').lower()
+        if rainbow_env in ('true', '1', 'yes'):
+            self.rainbow = True
+        elif rainbow_env in ('false', '0', 'no'):
+            self.rainbow = False
+        else:
+            self.rainbow = bool(display_config.get('rainbow', False))
         
         # Base font sizes (before scaling)
-        self.base_time_font_size = display_config.get('time_font_size', 280)
-        self.base_date_font_size = display_config.get('date_font_size', 90)
+        self.base_time_font_size = setting_number('TIME_FONT_SIZE', display_config, 'time_font_size', 280, minimum=10, maximum=1000)
+        self.base_date_font_size = setting_number('DATE_FONT_SIZE', display_config, 'date_font_size', 90, minimum=8, maximum=500)
         self.base_weather_font_size = display_config.get('weather_font_size', 60)
         self.status_font_size = 28
         
@@ -97,6 +325,7 @@ class FramebufferClock:
         self.display_scale = self.get_display_scale()
         self.time_font_size = max(10, int(self.base_time_font_size * self.display_scale))
         self.date_font_size = max(8, int(self.base_date_font_size * self.display_scale))
+        self.seconds_font_size = max(8, int(self.base_seconds_font_size * self.display_scale)) if self.base_seconds_font_size else 0
         self.weather_font_size = max(8, int(self.base_weather_font_size * self.display_scale))
         
         logging.info(f"Font sizes: time={self.time_font_size}, date={self.date_font_size}, weather={self.weather_font_size}, status={self.status_font_size}")
@@ -110,6 +339,16 @@ class FramebufferClock:
             self.format_12h = False
         else:
             self.format_12h = time_config.get('format_12h', True)
+        # TIME_FORMAT: "12"/"24" (as in the settings UI) or a full strftime pattern, e.g. "%H.%M"
+        self.time_format = None
+        time_format = str(os.environ.get('TIME_FORMAT') or time_config.get('format') or '').strip()
+        if time_format in ('12', '24'):
+            if format_12h_env not in ('true', '1', 'yes', 'false', '0', 'no'):
+                self.format_12h = time_format == '12'
+        elif '%' in time_format:
+            self.time_format = time_format
+        elif time_format:
+            logging.warning(f"Invalid TIME_FORMAT={time_format!r}: expected 12, 24 or a strftime pattern; ignoring")
         
         show_seconds_env = os.environ.get('SHOW_SECONDS', '').lower()
         if show_seconds_env in ('true', '1', 'yes'):
@@ -118,6 +357,102 @@ class FramebufferClock:
             self.show_seconds = False
         else:
             self.show_seconds = display_config.get('show_seconds', True)
+        if self.time_format:
+            # A custom pattern decides for itself; only redraw every second if it shows seconds
+            self.show_seconds = any(code in self.time_format for code in ('%S', '%T', '%X', '%r', '%c'))
+        # Smaller ":SS" right after the minutes, sharing the digits' baseline (0 = same size as the time);
+        # scaled per resolution like TIME_FONT_SIZE, never larger than the time, ignored with a TIME_FORMAT pattern
+        self.base_seconds_font_size = setting_number('SECONDS_SIZE', display_config, 'seconds_size', 0, minimum=0, maximum=1000)
+        # Countdown mode: until COUNTDOWN_TO the time line shows the remaining MM:SS (H:MM:SS above an hour),
+        # then the display flashes for countdown_alarm_seconds before going back to the clock
+        self.countdown_to = self._parse_countdown(os.environ.get('COUNTDOWN_TO') or time_config.get('countdown_to'))
+        self.countdown_alarm_seconds = setting_number('COUNTDOWN_ALARM_SECONDS', time_config, 'countdown_alarm_seconds', 60,
+                                                      minimum=0, maximum=3600)
+        # STROBE_HZ: how fast that alarm alternates full brightness and off (0 = stay lit without flashing)
+        self.strobe_hz = setting_number('STROBE_HZ', time_config, 'strobe_hz', 2.0, cast=float, minimum=0.0, maximum=10.0)
+        if self.countdown_to:
+            logging.info(f"Counting down to {self.countdown_to:%Y-%m-%d %H:%M:%S}")
+
+        # Blinking colon separator (toggles every second, layout width is preserved)
+        blink_colon_env = os.environ.get('BLINK_COLON', '').lower()
+        if blink_colon_env in ('true', '1', 'yes'):
+            self.blink_colon = True
+        elif blink_colon_env in ('false', '0', 'no'):
+            self.blink_colon = False
+        else:
+            self.blink_colon = display_config.get('blink_colon', False)
+        self._colon_visible = True
+        # Flash the whole time/date line at this rate in Hz, on for half of each period (0 = steady)
+        self.blink_time_hz = setting_number('BLINK_TIME', display_config, 'blink_time', 0, cast=float, minimum=0.0, maximum=10.0)
+        self.blink_date_hz = setting_number('BLINK_DATE', display_config, 'blink_date', 0, cast=float, minimum=0.0, maximum=10.0)
+        self._blink_shown = None  # (time on, date on) as of the last render
+
+        # Tabular digits: every digit gets the widest digit's advance so the time doesn't slide
+        tabular_env = os.environ.get('TABULAR_DIGITS', '').lower()
+        if tabular_env in ('true', '1', 'yes'):
+            self.tabular_digits = True
+        elif tabular_env in ('false', '0', 'no'):
+            self.tabular_digits = False
+        else:
+            self.tabular_digits = display_config.get('tabular_digits', False)
+
+        # Leading zero of a 24-hour time ("07:45"): show, hide, or dim to leading_zero_dim brightness
+        self.leading_zero = str(os.environ.get('LEADING_ZERO') or display_config.get('leading_zero', 'show')).lower()
+        if self.leading_zero not in ('show', 'hide', 'dim'):
+            logging.warning(f"Unknown LEADING_ZERO '{self.leading_zero}', using show")
+            self.leading_zero = 'show'
+        self.leading_zero_dim = setting_number('LEADING_ZERO_DIM', display_config, 'leading_zero_dim', 0.3,
+                                           cast=float, minimum=0.0, maximum=1.0)
+
+        # Gamma applied to glyph edge coverage (1.0 = linear; ~2.2 gives fuller antialiased edges)
+        self.gamma = setting_number('GAMMA', display_config, 'gamma', 1.0, cast=float, minimum=0.5, maximum=3.0)
+
+        # Time glyph style: "font" (TrueType), "segment" (drawn seven-segment digits and colon)
+        # or "analog" (a clock face in place of the digital time; the date still goes below it)
+        self.time_style = str(os.environ.get('TIME_STYLE') or display_config.get('time_style', 'font')).lower()
+        if self.time_style not in ('font', 'segment', 'analog'):
+            logging.warning(f"Unknown TIME_STYLE '{self.time_style}', using font")
+            self.time_style = 'font'
+        # Segment thickness as a fraction of digit height, slant in degrees (positive leans right)
+        self.segment_thickness = setting_number('SEGMENT_THICKNESS', display_config, 'segment_thickness', 0.12,
+                                            cast=float, minimum=0.03, maximum=0.3)
+        self.segment_slant = setting_number('SEGMENT_SLANT', display_config, 'segment_slant', 0,
+                                        cast=float, minimum=-20, maximum=20)
+
+        # Clock block alignment, e.g. "left top" (env var first, then config; default centered)
+        self.align_h, self.align_v = self.parse_alignment(
+            os.environ.get('CLOCK_ALIGN') or display_config.get('align', 'center middle'))
+        # Vertical layout: time and date stacked line_gap apart and centered as a block, unless
+        # TIME_OFFSET_Y/DATE_OFFSET_Y pin the old fixed offsets from the center line
+        self.line_gap = setting_number('LINE_GAP', display_config, 'line_gap', 40, minimum=0, maximum=1000)
+        self.fixed_offsets = bool(os.environ.get('TIME_OFFSET_Y') or os.environ.get('DATE_OFFSET_Y')
+                                  or 'time_offset_y' in display_config or 'date_offset_y' in display_config)
+        self.time_offset_y = setting_number('TIME_OFFSET_Y', display_config, 'time_offset_y', 60)
+        self.date_offset_y = setting_number('DATE_OFFSET_Y', display_config, 'date_offset_y', 100)
+        # Show or hide the time/date lines (env var first, then config; both shown by default)
+        for name in ('time', 'date'):
+            show_env = os.environ.get(f'SHOW_{name.upper()}', '').lower()
+            if show_env in ('true', '1', 'yes'):
+                show = True
+            elif show_env in ('false', '0', 'no'):
+                show = False
+            else:
+                show = display_config.get(f'show_{name}', True)
+            setattr(self, f'show_{name}', show)
+        # Per-line horizontal alignment (e.g. time left, date right); defaults to the block's
+        time_align = os.environ.get('TIME_ALIGN') or display_config.get('time_align')
+        date_align = os.environ.get('DATE_ALIGN') or display_config.get('date_align')
+        self.time_align_h = self.parse_alignment(time_align)[0] if time_align else self.align_h
+        self.date_align_h = self.parse_alignment(date_align)[0] if date_align else self.align_h
+        # Rectangular border drawn along the edge of the time/date canvas: "thickness,color" (unset = none)
+        self.border_time = self._parse_border(os.environ.get('BORDER_TIME') or display_config.get('border_time'), 'BORDER_TIME')
+        self.border_date = self._parse_border(os.environ.get('BORDER_DATE') or display_config.get('border_date'), 'BORDER_DATE')
+        # Absolute "x,y" top-left corner of the time/date canvas, bypassing alignment, margins and pixel shift
+        self.time_pos = self._parse_position(os.environ.get('TIME_POS') or display_config.get('time_pos'), 'TIME_POS')
+        self.date_pos = self._parse_position(os.environ.get('DATE_POS') or display_config.get('date_pos'), 'DATE_POS')
+
+        # Date format (strftime) - env var first, then config
+        self.date_format = os.environ.get('DATE_FORMAT') or display_config.get('date_format', "%A, %B %d, %Y")
 
         # Auto-shrink time when too wide (env or config; default enabled)
         auto_shrink_env = os.environ.get('AUTO_SHRINK_TIME', '').lower()
@@ -127,9 +462,28 @@ class FramebufferClock:
             self.auto_shrink_time = False
         else:
             self.auto_shrink_time = display_config.get('auto_shrink_time', True)
+        # Auto-fit the date: shrink its font when a long (e.g. localized) date would be clipped
+        auto_fit_env = os.environ.get('AUTO_FIT_DATE', '').lower()
+        if auto_fit_env in ('true', '1', 'yes'):
+            self.auto_fit_date = True
+        elif auto_fit_env in ('false', '0', 'no'):
+            self.auto_fit_date = False
+        else:
+            self.auto_fit_date = display_config.get('auto_fit_date', True)
+        # SIZE_AUTO_TIME: size the time font to fill this % of the screen width (0 = use TIME_FONT_SIZE)
+        self.size_auto_time = setting_number('SIZE_AUTO_TIME', display_config, 'size_auto_time', 0, minimum=0, maximum=100)
+        self._size_auto_cache = {}  # (digit pattern, fb width) -> font size
+        self._widest_time_digit = None
+        self._size_auto_applied = None
+        self._fit_width = None  # Usable width between margins, set each render
+        self._date_fit_cache = {}  # (text, requested size, fb width) -> fitted size
+        self._time_size_cap = None  # Set once the time font has been shrunk to fit
         
         # Initialize fonts
         self.init_fonts()
+        self._requested_date_font_size = self.date_font_size
+        if self.size_auto_time:
+            self._apply_size_auto_time(self.format_time(datetime.now()))
 
         # Cache helpers (init before prerender to avoid AttributeError)
         self._bbox_cache = {}  # Cache text bboxes
@@ -139,8 +493,14 @@ class FramebufferClock:
             self._temp_draw = None
 
         # Pre-render sprite cache for fast compositing (7-15x faster than text rendering)
+        # _sprite_cache is the active time set; _time_sprite_sets keeps recent sizes by font size
         self._sprite_cache = {}
-        self._prerender_time_sprites()
+        self._time_sprite_sets = OrderedDict()
+        self._time_sprite_set = None
+        self._date_sprite_cache = OrderedDict()
+        self.sprite_cache_hits = 0
+        self.sprite_cache_misses = 0
+        self._use_time_sprites()
         
         # Status bar configuration
         self.show_status_bar = True
@@ -168,8 +528,8 @@ class FramebufferClock:
             self.screensaver_enabled = False
         else:
             self.screensaver_enabled = display_config.get('screensaver_enabled', True)
-        self.screensaver_start = int(os.environ.get('SCREENSAVER_START_HOUR', display_config.get('screensaver_start_hour', 2)))
-        self.screensaver_end = int(os.environ.get('SCREENSAVER_END_HOUR', display_config.get('screensaver_end_hour', 5)))
+        self.screensaver_start = setting_number('SCREENSAVER_START_HOUR', display_config, 'screensaver_start_hour', 2, minimum=0, maximum=24)
+        self.screensaver_end = setting_number('SCREENSAVER_END_HOUR', display_config, 'screensaver_end_hour', 5, minimum=0, maximum=24)
         
         # Night dimming configuration - check env vars first
         dim_at_night_env = os.environ.get('DIM_AT_NIGHT', '').lower()
@@ -179,11 +539,58 @@ class FramebufferClock:
             self.dim_at_night = False
         else:
             self.dim_at_night = display_config.get('dim_at_night', True)
-        self.night_brightness = float(os.environ.get('NIGHT_BRIGHTNESS', display_config.get('night_brightness', 0.3)))
-        self.night_start = int(os.environ.get('NIGHT_START_HOUR', display_config.get('night_start_hour', 22)))
-        self.night_end = int(os.environ.get('NIGHT_END_HOUR', display_config.get('night_end_hour', 6)))
+        self.night_brightness = setting_number('NIGHT_BRIGHTNESS', display_config, 'night_brightness', 0.3, cast=float, minimum=0.0, maximum=1.0)
+        self.night_start = setting_number('NIGHT_START_HOUR', display_config, 'night_start_hour', 22, minimum=0, maximum=24)
+        self.night_end = setting_number('NIGHT_END_HOUR', display_config, 'night_end_hour', 6, minimum=0, maximum=24)
+        # Optional warm night color and gradual day/night fade
+        night_color = os.environ.get('NIGHT_COLOR') or display_config.get('night_color')
+        self.night_color = self.hex_to_rgb(night_color, fallback=None, name='NIGHT_COLOR') if night_color else None
+        # Night color temperature in kelvin (0 = off): multiplies the day/night color, e.g. 2700 = warm
+        self.night_color_temp = setting_number('NIGHT_COLOR_TEMP', display_config, 'night_color_temp', 0, minimum=0, maximum=40000)
+        self._night_temp_factors = tuple(c / 255.0 for c in kelvin_to_rgb(self.night_color_temp)) if self.night_color_temp else None
+        self.night_fade_seconds = setting_number('NIGHT_FADE_SECONDS', display_config, 'night_fade_seconds', 0, cast=float, minimum=0.0)
+        self._night_level = 0.0  # 0 = day, 1 = night
+        self._night_level_updated = None
         self.current_brightness = 1.0
         
+        # Optional hardware backlight (/sys/class/backlight/<name>) following the same brightness level
+        self.backlight_device = os.environ.get('BACKLIGHT_DEVICE') or display_config.get('backlight_device')
+        backlight_limit = self._read_backlight('max_brightness') if self.backlight_device else None
+        self._backlight_initial = self._read_backlight('brightness') if backlight_limit else None
+        self._backlight_value = None
+        self.brightness_mode = str(os.environ.get('BRIGHTNESS_MODE') or display_config.get(
+            'brightness_mode', 'both' if backlight_limit else 'sw')).lower()
+        if self.brightness_mode not in ('sw', 'hw', 'both'):
+            logging.warning(f"Unknown BRIGHTNESS_MODE '{self.brightness_mode}', using sw")
+            self.brightness_mode = 'sw'
+        if self.brightness_mode != 'sw' and not backlight_limit:
+            logging.warning(f"BRIGHTNESS_MODE={self.brightness_mode} needs a readable BACKLIGHT_DEVICE, using sw")
+            self.brightness_mode = 'sw'
+        if backlight_limit:
+            self.backlight_min = setting_number('BACKLIGHT_MIN', display_config, 'backlight_min', 0, minimum=0, maximum=backlight_limit)
+            self.backlight_max = setting_number('BACKLIGHT_MAX', display_config, 'backlight_max', backlight_limit,
+                                            minimum=self.backlight_min, maximum=backlight_limit)
+            logging.info(f"Backlight: {self.backlight_device} ({self.backlight_min}-{self.backlight_max}), mode {self.brightness_mode}")
+        
+        # Optional BH1750 ambient light sensor; its lux->brightness curve replaces the night schedule
+        # for brightness (night color still follows the schedule) - lazy load
+        self.light_sensor = None
+        self._autobright_level = None
+        autobright_bus = os.environ.get('AUTOBRIGHT_I2C') or display_config.get('autobright_i2c')
+        if autobright_bus:
+            global light_sensor
+            try:
+                if light_sensor is None:
+                    import light_sensor
+                self.autobright_curve = light_sensor.parse_curve(
+                    os.environ.get('AUTOBRIGHT_CURVE') or display_config.get('autobright_curve', light_sensor.DEFAULT_CURVE))
+                self.autobright_hysteresis = setting_number('AUTOBRIGHT_HYSTERESIS', display_config, 'autobright_hysteresis', 0.05,
+                                                        cast=float, minimum=0.0, maximum=1.0)
+                address = int(str(os.environ.get('AUTOBRIGHT_ADDRESS') or display_config.get('autobright_address', '0x23')), 0)
+                self.light_sensor = light_sensor.LightSensor(autobright_bus, address)
+            except (ImportError, OSError, ValueError) as e:
+                logging.warning(f"Auto-brightness disabled: {e}")
+        
         # Pixel shift configuration - check env vars first
         pixel_shift_env = os.environ.get('PIXEL_SHIFT_ENABLED', '').lower()
         if pixel_shift_env in ('true', '1', 'yes'):
@@ -192,13 +599,20 @@ class FramebufferClock:
             self.pixel_shift_enabled = False
         else:
             self.pixel_shift_enabled = display_config.get('pixel_shift_enabled', True)
-        self.pixel_shift_interval = int(os.environ.get('PIXEL_SHIFT_INTERVAL_SECONDS', display_config.get('pixel_shift_interval_seconds', 30)))  # Shift every 30s for better burn-in protection
-        self.pixel_shift_disable_start = int(os.environ.get('PIXEL_SHIFT_DISABLE_START_HOUR', display_config.get('pixel_shift_disable_start_hour', 12)))
-        self.pixel_shift_disable_end = int(os.environ.get('PIXEL_SHIFT_DISABLE_END_HOUR', display_config.get('pixel_shift_disable_end_hour', 14)))
+        self.pixel_shift_interval = setting_number('PIXEL_SHIFT_INTERVAL_SECONDS', display_config, 'pixel_shift_interval_seconds', 30, minimum=1)  # Shift every 30s for better burn-in protection
+        self.pixel_shift_disable_start = setting_number('PIXEL_SHIFT_DISABLE_START_HOUR', display_config, 'pixel_shift_disable_start_hour', 12, minimum=0, maximum=24)
+        self.pixel_shift_disable_end = setting_number('PIXEL_SHIFT_DISABLE_END_HOUR', display_config, 'pixel_shift_disable_end_hour', 14, minimum=0, maximum=24)
         self.last_pixel_shift = 0
         self.pixel_shift_x = 0
         self.pixel_shift_y = 0
-        self.pixel_shift_max = 50  # Increased to ±50px (100px total range) for better burn-in protection
+        # ±range in pixels (default ±50px, 100px total range for better burn-in protection)
+        self.pixel_shift_max = setting_number('PIXEL_SHIFT_RANGE', display_config, 'pixel_shift_range', 50, minimum=0, maximum=200)
+        # Deterministic walk: a stride coprime with the grid size visits every offset once per cycle
+        grid_cells = (2 * self.pixel_shift_max + 1) ** 2
+        self._pixel_shift_stride = max(1, int(grid_cells * 0.618))
+        while math.gcd(self._pixel_shift_stride, grid_cells) != 1:
+            self._pixel_shift_stride += 1
+        self._pixel_shift_step = 0
         # Track previous shift to detect changes and clear artifacts
         self._prev_pixel_shift_x = 0
         self._prev_pixel_shift_y = 0
@@ -239,6 +653,19 @@ class FramebufferClock:
         # Weather update tracking
         self.last_weather_update = 0
         self.weather_text = ""
+        # Extra static labels (TEXT_BOXES JSON or display.text_boxes), drawn after the weather line
+        self.text_boxes = self._parse_text_boxes(os.environ.get('TEXT_BOXES') or display_config.get('text_boxes'))
+        # Each line of EXTRA_LINES_FILE (e.g. written by a sensor script) is stacked under the date;
+        # the file is re-read whenever it changes, and an empty or missing file shows nothing
+        self.extra_lines_file = os.environ.get('EXTRA_LINES_FILE') or display_config.get('extra_lines_file')
+        self.extra_lines_size = max(8, int(setting_number('EXTRA_LINES_SIZE', display_config, 'extra_lines_size', 60,
+                                                          minimum=8, maximum=500) * self.display_scale))
+        extra_lines_color = os.environ.get('EXTRA_LINES_COLOR') or display_config.get('extra_lines_color')
+        self.extra_lines_color = (self.hex_to_rgb(extra_lines_color, fallback=self.date_color, name='EXTRA_LINES_COLOR')
+                                  if extra_lines_color else self.date_color)
+        self.extra_lines_gap = setting_number('EXTRA_LINES_GAP', display_config, 'extra_lines_gap', 20, minimum=0, maximum=500)
+        self._extra_lines = []  # text box dicts, one per shown line
+        self._extra_lines_mtime = None
         
         # Initialize RTC manager only if enabled - lazy load
         rtc_enabled = os.environ.get('RTC_ENABLED', '').lower() in ('true', '1', 'yes') or config.get('time', {}).get('rtc_enabled', False)
@@ -285,25 +712,521 @@ class FramebufferClock:
         self._last_date_sent = None
         logging.info("Framebuffer clock initialized")
     
+    def _sysfs_path(self, attr: str) -> str:
+        """Path of a sysfs attribute for the configured framebuffer (e.g. fb0, fb1)."""
+        return f"/sys/class/graphics/{os.path.basename(self.fb_device)}/{attr}"
+
+    def _map_framebuffer(self):
+        """Memory-map the framebuffer for partial writes; leaves fb_mmap None if unavailable."""
+        self.fb_mmap = None
+        self._fb_file = None
+        if self._png_dir:
+            return
+        try:
+            if self.fb_format:
+                fb_size = self._fb_stride_bytes * self._fb_phys_height
+                if self._fb_fix and self._fb_fix['smem_len'] and fb_size > self._fb_fix['smem_len']:
+                    raise ValueError(f"computed size {fb_size} exceeds framebuffer memory "
+                                     f"{self._fb_fix['smem_len']} (stride={self._fb_stride_bytes}, rows={self._fb_phys_height})")
+                fb = open(self.fb_device, 'r+b', buffering=0)
+                self.fb_mmap = mmap.mmap(fb.fileno(), fb_size, access=mmap.ACCESS_WRITE)
+                self._fb_file = fb  # keep file open for mapping lifetime
+                logging.info(f"{self.fb_device} memory-mapped for fast partial updates")
+        except Exception as e:
+            self.fb_mmap = None
+            self._fb_file = None
+            logging.warning(f"Framebuffer mmap not available, falling back to writes: {e}")
+
+    def _unmap_framebuffer(self):
+        """Close the framebuffer mapping and its file handle, ignoring errors."""
+        try:
+            if getattr(self, 'fb_mmap', None):
+                self.fb_mmap.close()
+            if getattr(self, '_fb_file', None):
+                self._fb_file.close()
+        except Exception:
+            pass
+        self.fb_mmap = None
+        self._fb_file = None
+
+    def reopen_framebuffer(self) -> bool:
+        """Re-query and re-map the framebuffer after a write error (e.g. HDMI unplug/replug).
+        Re-detects size and pixel format (a replugged monitor may come back at another mode),
+        rebuilds the shadow buffer and background if either changed, and queues a full redraw.
+        Attempts are rate-limited with exponential backoff.
+        """
+        now = time.monotonic()
+        if now < self._fb_reopen_at:
+            return False
+        self._unmap_framebuffer()
+        try:
+            if not os.path.exists(self.fb_device):
+                raise FileNotFoundError(f"{self.fb_device} not present")
+            self._fb_var = self.get_var_screeninfo()
+            phys_width, phys_height = self.get_framebuffer_size()
+            old_format = (self.fb_format, self.fb_bpp, getattr(self, '_fb_channels', None), self._fb_alpha_mask)
+            self._detect_pixel_format()
+            format_changed = (self.fb_format, self.fb_bpp, getattr(self, '_fb_channels', None), self._fb_alpha_mask) != old_format
+            if format_changed:
+                logging.info(f"Framebuffer pixel format changed: {old_format[0] or f'{old_format[1]}bpp'} → "
+                             f"{self.fb_format or f'{self.fb_bpp}bpp'}")
+            size_changed = (phys_width, phys_height) != (self._fb_phys_width, self._fb_phys_height)
+            if size_changed:
+                logging.info(f"Framebuffer resolution changed: {self._fb_phys_width}x{self._fb_phys_height} → {phys_width}x{phys_height}")
+                self._fb_phys_width, self._fb_phys_height = phys_width, phys_height
+                self.fb_width, self.fb_height = self._logical_size(phys_width, phys_height)
+            if size_changed or format_changed:
+                self.fb_shadow = np.full((self.fb_height, self.fb_width), self._fb_alpha_mask, dtype=self._shadow_dtype())
+                self._bg_native = self._native_color(self.bg_color)
+                self._bg_image = self.load_background_image(self._bg_image_path)
+                self._clear_shadow()
+                self._last_time_rect = None
+                self._last_date_rect = None
+                self._last_status_rect = None
+                if hasattr(self, '_last_weather_rect'):
+                    self._last_weather_rect = None
+                for box in self.text_boxes + self._extra_lines:
+                    setattr(self, box['rect_attr'], None)
+            self._fb_fix = self.get_fix_screeninfo()
+            self._fb_stride_bytes = self.get_stride_bytes()
+            self._map_framebuffer()
+        except Exception as e:
+            logging.warning(f"Framebuffer reopen failed, retrying in {self._fb_reopen_delay:.2f}s: {e}")
+            self._fb_reopen_at = now + self._fb_reopen_delay
+            self._fb_reopen_delay = min(FB_REOPEN_MAX_DELAY, self._fb_reopen_delay * 2)
+            return False
+        logging.info(f"Reopened framebuffer {self.fb_device} ({self._fb_phys_width}x{self._fb_phys_height})")
+        self._fb_reopen_delay = FB_REOPEN_INITIAL_DELAY
+        self._fb_reopen_at = 0.0
+        self._dirty_rects.clear()
+        self._full_redraw = True
+        return True
+
+    def get_rotation(self, display_config: dict) -> int:
+        """Clockwise output rotation (0/90/180/270) from ROTATION or DISPLAY_ORIENTATION.
+        ROTATION env wins, then DISPLAY_ORIENTATION env, then a non-zero display.rotation,
+        then display.orientation; portrait is shorthand for 90.
+        """
+        orientation = str(os.environ.get('DISPLAY_ORIENTATION') or display_config.get('orientation') or 'landscape').strip().lower()
+        if orientation not in ('landscape', 'portrait'):
+            logging.warning(f"DISPLAY_ORIENTATION={orientation} must be landscape or portrait; using landscape")
+        # display.rotation: 0 (the shipped default) defers to orientation
+        use_config = display_config.get('rotation') and not os.environ.get('DISPLAY_ORIENTATION')
+        rotation = setting_number('ROTATION', display_config if use_config else {}, 'rotation',
+                                  90 if orientation == 'portrait' else 0)
+        if orientation == 'portrait' and not use_config and not os.environ.get('ROTATION', '').strip():
+            # Earlier releases ignored orientation, so a panel already rotated at boot would now turn twice
+            logging.warning("Orientation portrait now rotates the clock output 90°; if the panel is already "
+                            "rotated (e.g. display_rotate in config.txt), set ROTATION=0")
+        if rotation % 360 not in (0, 90, 180, 270):
+            logging.warning(f"ROTATION={rotation} must be 0, 90, 180 or 270; not rotating")
+            return 0
+        return rotation % 360
+
+    def _logical_size(self, phys_width: int, phys_height: int):
+        """Logical canvas size for the configured rotation."""
+        if self.rotation in (90, 270):
+            return phys_height, phys_width
+        return phys_width, phys_height
+
+    def _to_physical(self, block: np.ndarray, x: int, y: int):
+        """Rotate a logical shadow block at (x, y) into physical orientation.
+        Returns (block, physical_x, physical_y) for writing rows at the physical offset.
+        """
+        h, w = block.shape
+        if self.rotation == 90:
+            return np.rot90(block, -1), self.fb_height - y - h, x
+        if self.rotation == 180:
+            return np.rot90(block, 2), self.fb_width - x - w, self.fb_height - y - h
+        if self.rotation == 270:
+            return np.rot90(block, 1), y, self.fb_width - x - w
+        return block, x, y
+
     def get_framebuffer_size(self):
-        """Get framebuffer dimensions."""
+        """Get framebuffer dimensions: ioctl first, then sysfs, then a common default.
+        The PNG backend takes its size from PNG_SIZE (e.g. "800x480").
+        """
+        if self._fb_size_override:
+            return self._fb_size_override
+        if self._png_dir:
+            try:
+                w, h = os.environ.get('PNG_SIZE', '1920x1200').lower().split('x')
+                return int(w), int(h)
+            except ValueError:
+                logging.warning(f"Invalid PNG_SIZE={os.environ.get('PNG_SIZE')!r}, using 1920x1200")
+                return 1920, 1200
+        var = self._fb_var
+        if var and var['xres_virtual'] and var['yres_virtual']:
+            return var['xres_virtual'], var['yres_virtual']
         try:
-            with open('/sys/class/graphics/fb0/virtual_size', 'r') as f:
+            with open(self._sysfs_path('virtual_size'), 'r') as f:
                 w, h = f.read().strip().split(',')
                 return int(w), int(h)
         except:
             # Fallback to common size
+            logging.warning("Framebuffer size unavailable from ioctl and sysfs, assuming 1920x1200")
             return 1920, 1200
     
+    def get_var_screeninfo(self) -> Optional[dict]:
+        """Query fb_var_screeninfo via the FBIOGET_VSCREENINFO ioctl.
+        Returns resolution, bits-per-pixel and RGB/alpha bitfields, or None if the ioctl fails.
+        """
+        try:
+            with open(self.fb_device, 'rb') as fb:
+                buf = fcntl.ioctl(fb.fileno(), FBIOGET_VSCREENINFO, bytes(FB_VAR_SCREENINFO_SIZE))
+            fields = struct.unpack(f'{FB_VAR_SCREENINFO_SIZE // 4}I', buf)
+        except Exception as e:
+            logging.debug(f"FBIOGET_VSCREENINFO unavailable on {self.fb_device}: {e}")
+            return None
+        # Layout: xres, yres, xres_virtual, yres_virtual, xoffset, yoffset,
+        # bits_per_pixel, grayscale, then red/green/blue/transp {offset, length, msb_right}
+        return {
+            'xres': fields[0],
+            'yres': fields[1],
+            'xres_virtual': fields[2],
+            'yres_virtual': fields[3],
+            'bits_per_pixel': fields[6],
+            'red': (fields[8], fields[9]),
+            'green': (fields[11], fields[12]),
+            'blue': (fields[14], fields[15]),
+            'transp': (fields[17], fields[18]),
+        }
+
+    def get_fix_screeninfo(self) -> Optional[dict]:
+        """Query fb_fix_screeninfo via the FBIOGET_FSCREENINFO ioctl, or None if it fails."""
+        size = struct.calcsize(FB_FIX_SCREENINFO_FORMAT)
+        try:
+            with open(self.fb_device, 'rb') as fb:
+                buf = fcntl.ioctl(fb.fileno(), FBIOGET_FSCREENINFO, bytes(size))
+            fields = struct.unpack(FB_FIX_SCREENINFO_FORMAT, buf)
+        except Exception as e:
+            logging.debug(f"FBIOGET_FSCREENINFO unavailable on {self.fb_device}: {e}")
+            return None
+        # Layout: id, smem_start, smem_len, type, type_aux, visual,
+        # xpanstep, ypanstep, ywrapstep, line_length, ...
+        return {
+            'id': fields[0].rstrip(b'\0').decode('ascii', 'replace'),
+            'smem_len': fields[2],
+            'line_length': fields[9],
+        }
+
+    def get_stride_bytes(self) -> int:
+        """Bytes per framebuffer row: ioctl line_length, then sysfs stride, else width * bpp."""
+        row_bytes = self._fb_phys_width * self._fb_bytes_per_pixel
+        line_length = self._fb_fix['line_length'] if self._fb_fix else 0
+        if not line_length:
+            try:
+                with open(self._sysfs_path('stride'), 'r') as f:
+                    line_length = int(f.read().strip())
+            except Exception:
+                line_length = 0
+        if line_length >= row_bytes:
+            if line_length != row_bytes:
+                logging.info(f"Framebuffer rows are padded: line_length={line_length} (width*bpp={row_bytes})")
+            return line_length
+        if line_length:
+            logging.warning(f"Ignoring line_length={line_length} smaller than width*bpp={row_bytes}")
+        return row_bytes
+
     def get_bits_per_pixel(self) -> int:
-        """Read framebuffer bits-per-pixel from sysfs, default to 16 if unknown."""
+        """Read framebuffer bits-per-pixel from the ioctl, then sysfs, default to 16 if unknown."""
+        if self._fb_var and self._fb_var['bits_per_pixel']:
+            return self._fb_var['bits_per_pixel']
         try:
-            with open('/sys/class/graphics/fb0/bits_per_pixel', 'r') as f:
+            with open(self._sysfs_path('bits_per_pixel'), 'r') as f:
                 bpp = int(f.read().strip())
                 return bpp
         except Exception:
             return 16
 
+    def get_pixel_format(self) -> Optional[str]:
+        """Resolve framebuffer pixel format from FB_FORMAT env var or bits-per-pixel.
+        Returns a key of PIXEL_FORMATS, or None if the format is not supported.
+        """
+        env_format = os.environ.get('FB_FORMAT', '').upper().strip()
+        if env_format:
+            if env_format in PIXEL_FORMATS:
+                kernel_bpp = self._fb_var['bits_per_pixel'] if self._fb_var else None
+                if kernel_bpp and kernel_bpp != PIXEL_FORMATS[env_format] * 8:
+                    # Never trust a guess that would mmap the wrong buffer length
+                    logging.error(f"FB_FORMAT={env_format} conflicts with {kernel_bpp}bpp reported by "
+                                  f"{self.fb_device}; ignoring override")
+                else:
+                    return env_format
+            else:
+                logging.warning(f"Unknown FB_FORMAT={env_format}, detecting from bits-per-pixel")
+        if self.fb_bpp == 32 and self._fb_var:
+            # Tell 32bpp layouts apart by the kernel bitfields: red in the top byte is RGBA,
+            # a non-empty transp field is ARGB, anything else is treated as XRGB
+            if self._fb_var['red'][0] == 24:
+                return 'RGBA8888'
+            if self._fb_var['transp'][1]:
+                return 'ARGB8888'
+        return {16: 'RGB565', 32: 'XRGB8888'}.get(self.fb_bpp)
+
+    def _detect_pixel_format(self):
+        """Set fb_bpp, fb_format, channel layout and bytes-per-pixel from the current device."""
+        self.fb_bpp = self.get_bits_per_pixel()
+        self.fb_format = self.get_pixel_format()
+        if self.fb_format:
+            self.fb_bpp = PIXEL_FORMATS[self.fb_format] * 8
+            self._fb_channels = self.get_channel_layout()
+        else:
+            logging.warning(f"Optimized blitter supports {', '.join(PIXEL_FORMATS)}; {self.fb_bpp}bpp will fallback to full-frame writes")
+        self._fb_bytes_per_pixel = PIXEL_FORMATS.get(self.fb_format, 2)
+        self._fb_alpha_mask = self.get_alpha_mask()
+
+    def _shadow_dtype(self) -> str:
+        """numpy dtype of one native pixel in the shadow buffer."""
+        return '<u4' if self._fb_bytes_per_pixel == 4 else '<u2'
+
+    def _native_color(self, rgb: tuple) -> int:
+        """Pack a single (r, g, b) color into a native pixel value (0 if the format is unsupported)."""
+        if not self.fb_format:
+            return 0
+        return int(self._pack_rgb(np.array([[rgb]], dtype=np.uint8))[0, 0])
+
+    def get_channel_layout(self) -> tuple:
+        """Return ((offset, length), ...) for R, G, B within a native pixel word.
+        Uses the kernel-reported bitfields when sane, else the format's default layout.
+        """
+        default = PIXEL_FORMAT_CHANNELS[self.fb_format]
+        var = self._fb_var
+        if not var or os.environ.get('FB_FORMAT'):
+            return default
+        channels = (var['red'], var['green'], var['blue'])
+        bpp = PIXEL_FORMATS[self.fb_format] * 8
+        if any(length == 0 or length > 8 or offset + length > bpp for offset, length in channels):
+            logging.warning(f"Ignoring unusable RGB bitfields from {self.fb_device}: {channels}")
+            return default
+        if channels != default:
+            logging.info(f"Framebuffer channel layout (offset, length) R={channels[0]} G={channels[1]} B={channels[2]}")
+        return channels
+
+    def get_alpha_mask(self) -> int:
+        """Native bits to set on every pixel so formats with alpha stay opaque (0 if none).
+        Uses the kernel-reported transp bitfield when sane, else the format's default.
+        """
+        if not self.fb_format:
+            return 0
+        offset, length = PIXEL_FORMAT_ALPHA.get(self.fb_format, (0, 0))
+        var = self._fb_var
+        if var and not os.environ.get('FB_FORMAT') and var['transp'][1]:
+            t_offset, t_length = var['transp']
+            if t_offset + t_length <= PIXEL_FORMATS[self.fb_format] * 8:
+                offset, length = t_offset, t_length
+        return ((1 << length) - 1) << offset
+
+    def _pack_rgb(self, arr: np.ndarray) -> np.ndarray:
+        """Pack an (h, w, 3) uint8 RGB array into the framebuffer's native pixel format."""
+        dtype = np.uint32 if self._fb_bytes_per_pixel == 4 else np.uint16
+        packed = np.full(arr.shape[:2], self._fb_alpha_mask, dtype=dtype)
+        for channel, (offset, length) in enumerate(self._fb_channels):
+            packed |= (arr[:, :, channel].astype(dtype) >> (8 - length)) << offset
+        return packed
+
+    def _native_to_rgb888(self, packed: np.ndarray) -> np.ndarray:
+        """Unpack native framebuffer pixels to (h, w, 3) uint8, scaling each channel to 0-255."""
+        rgb = np.empty(packed.shape + (3,), dtype=np.uint8)
+        for channel, (offset, length) in enumerate(self._fb_channels):
+            mask = (1 << length) - 1
+            rgb[:, :, channel] = ((packed.astype(np.uint32) >> offset) & mask) * 255 // mask
+        return rgb
+
+    def write_snapshot(self, path: str):
+        """Save the shadow buffer as a raw P6 PPM, in physical panel orientation like the PNG backend."""
+        shadow, _, _ = self._to_physical(self.fb_shadow, 0, 0)
+        height, width = shadow.shape
+        try:
+            with open(path, 'wb') as f:
+                f.write(f"P6\n{width} {height}\n255\n".encode('ascii'))
+                f.write(self._native_to_rgb888(shadow).tobytes())
+            logging.info(f"Snapshot written to {path} ({width}x{height})")
+        except OSError as e:
+            logging.warning(f"Could not write snapshot to {path}: {e}")
+
+    def _write_png_frame(self):
+        """PNG backend: save the shadow buffer as the next numbered frame if anything changed."""
+        if not (self._full_redraw or self._dirty_rects):
+            return
+        shadow, _, _ = self._to_physical(self.fb_shadow, 0, 0)
+        path = os.path.join(self._png_dir, f"frame_{self._png_frame:06d}.png")
+        Image.fromarray(self._native_to_rgb888(shadow), 'RGB').save(path)
+        self._png_frame += 1
+        self.fb_bytes_written += shadow.nbytes
+        self._dirty_rects.clear()
+        self._full_redraw = False
+
+    def _rgb565_to_native(self, rgb565: np.ndarray) -> np.ndarray:
+        """Convert a pre-converted RGB565 sprite/canvas to the framebuffer's native format.
+        Standard RGB565 is returned untouched; anything else expands 5/6-bit channels back to 8 bits.
+        """
+        if self._fb_channels == PIXEL_FORMAT_CHANNELS['RGB565']:
+            return rgb565
+        return self._pack_rgb(self._rgb565_to_rgb888(rgb565))
+
+    @staticmethod
+    def _rgb565_to_rgb888(rgb565: np.ndarray) -> np.ndarray:
+        """Expand an RGB565 array to (h, w, 3) uint8, replicating high bits into the low ones."""
+        rgb565 = rgb565.astype(np.uint16)
+        rgb = np.empty(rgb565.shape + (3,), dtype=np.uint8)
+        r = (rgb565 >> 11) & 0x1F
+        g = (rgb565 >> 5) & 0x3F
+        b = rgb565 & 0x1F
+        rgb[:, :, 0] = (r << 3) | (r >> 2)
+        rgb[:, :, 1] = (g << 2) | (g >> 4)
+        rgb[:, :, 2] = (b << 3) | (b >> 2)
+        return rgb
+
+    def load_background_image(self, path: Optional[str]) -> Optional[np.ndarray]:
+        """Load an image scaled/cropped to fill the logical canvas, in native pixel format.
+        Returns None (solid bg_color) if no path is set or the image can't be used.
+        """
+        if not path or not self.fb_format:
+            return None
+        try:
+            with Image.open(path) as img:
+                fitted = ImageOps.fit(img.convert('RGB'), (self.fb_width, self.fb_height))
+            arr = np.asarray(fitted, dtype=np.uint8)
+            logging.info(f"Background image: {path} ({img.width}x{img.height} → {self.fb_width}x{self.fb_height})")
+            return self._pack_rgb(arr).astype(self.fb_shadow.dtype)
+        except Exception as e:
+            logging.warning(f"BG_IMAGE={path} could not be loaded ({e}), using background color")
+            return None
+
+    def _has_background(self) -> bool:
+        """True if clears and text blending use something other than black."""
+        return self._bg_image is not None or self.bg_color != (0, 0, 0)
+
+    def _clear_shadow(self, x1: int = 0, y1: int = 0, x2: Optional[int] = None, y2: Optional[int] = None):
+        """Reset a shadow region (default: everything) to the background image or color."""
+        x2 = self.fb_width if x2 is None else x2
+        y2 = self.fb_height if y2 is None else y2
+        if self._bg_image is not None:
+            self.fb_shadow[y1:y2, x1:x2] = self._bg_image[y1:y2, x1:x2]
+        else:
+            self.fb_shadow[y1:y2, x1:x2].fill(self._bg_native)
+
+    def _blend_over_background(self, rgb: np.ndarray, color: tuple, x: int, y: int,
+                               alpha: Optional[np.ndarray] = None) -> np.ndarray:
+        """Interpolate between the background under (x, y) and the text color by glyph coverage.
+        rgb: (h, w, 3) uint8 text rendered on black, i.e. each pixel is color * coverage.
+        alpha: (h, w) uint8 coverage for multi-colored text (e.g. a dark shadow), else derived
+        from rgb against color.
+        """
+        if not self._has_background():
+            return rgb
+        h, w = rgb.shape[:2]
+        if self._bg_image is not None:
+            background = self._native_to_rgb888(self._bg_image[y:y+h, x:x+w]).astype(np.float32)
+        else:
+            background = np.array(self.bg_color, dtype=np.float32)
+        if alpha is not None:
+            coverage = alpha[:, :, None] / 255.0
+        else:
+            coverage = np.clip(rgb.max(axis=2, keepdims=True) / max(1, max(color)), 0.0, 1.0)
+        out = rgb + background * (1.0 - coverage)
+        return np.clip(out, 0, 255).astype(np.uint8)
+
+    @staticmethod
+    def _rgb888_to_rgb565(rgb: np.ndarray) -> np.ndarray:
+        """Pack an (h, w, 3) uint8 array into standard RGB565."""
+        rgb = rgb.astype(np.uint16)
+        return ((rgb[:, :, 0] >> 3) << 11) | ((rgb[:, :, 1] >> 2) << 5) | (rgb[:, :, 2] >> 3)
+
+    @staticmethod
+    def _same_hue(color: tuple, base: tuple) -> bool:
+        """True if color is base scaled by a single brightness factor (within rounding)."""
+        factor = max(color) / max(1, max(base))
+        return all(abs(c - b * factor) <= 1 for c, b in zip(color, base))
+
+    def _recolor_rgb565(self, rgb565: np.ndarray, color: tuple, base: tuple) -> np.ndarray:
+        """Repaint an RGB565 sprite rendered in base as color, keeping each pixel's coverage."""
+        rgb = self._rgb565_to_rgb888(rgb565)
+        coverage = rgb.max(axis=2, keepdims=True) / max(1, max(base))
+        out = np.clip(coverage * np.array(color, dtype=np.float32), 0, 255).astype(np.uint8)
+        return self._rgb888_to_rgb565(out)
+
+    @staticmethod
+    def _scale_rgb565(rgb565: np.ndarray, factor: float) -> np.ndarray:
+        """Scale RGB565 pixels by a brightness factor (extract components, scale, recombine)."""
+        r = ((rgb565 >> 11) & 0x1F)
+        g = ((rgb565 >> 5) & 0x3F)
+        b = (rgb565 & 0x1F)
+        r = (r * factor).astype(np.uint16).clip(0, 31)
+        g = (g * factor).astype(np.uint16).clip(0, 63)
+        b = (b * factor).astype(np.uint16).clip(0, 31)
+        return (r << 11) | (g << 5) | b
+
+    @staticmethod
+    def parse_alignment(value: str):
+        """Parse "<left|center|right> <top|middle|bottom>" (either order, either part optional)."""
+        align_h, align_v = 'center', 'middle'
+        for token in str(value).lower().replace(',', ' ').split():
+            if token in ('left', 'center', 'right'):
+                align_h = token
+            elif token in ('top', 'middle', 'bottom'):
+                align_v = token
+            else:
+                logging.warning(f"Ignoring unknown alignment '{token}' in '{value}'")
+        return align_h, align_v
+
+    def _aligned_x(self, width: int, center_x: int, margin: int, align: Optional[str] = None) -> int:
+        """Left edge of a canvas under align (default align_h); pixel shift still moves pinned edges inwards."""
+        align = align or self.align_h
+        shift = abs(center_x - self.fb_width // 2)
+        if align == 'left':
+            return margin + shift
+        if align == 'right':
+            return self.fb_width - margin - width - shift
+        return center_x - (width // 2)
+
+    def _stack_offsets(self, time_block_h: int, date_block_h: int) -> tuple:
+        """(time_offset_y, date_offset_y) from the block's center line: the time's center sits
+        time_offset_y above it and the date's top date_offset_y below it.
+        """
+        if self.fixed_offsets and self.show_time and self.show_date:
+            return int(self.time_offset_y * self.display_scale), int(self.date_offset_y * self.display_scale)
+        # Offsets that center the stacked pair
+        gap = int(self.line_gap * self.display_scale) if self.show_time and self.show_date else 0
+        block_h = time_block_h + gap + date_block_h
+        return block_h // 2 - time_block_h // 2, block_h - date_block_h - block_h // 2
+    
+    def _place_time_date(self, time_size: tuple, date_size: tuple, center_x_time: int, center_x: int,
+                         center_y: int, margin: int) -> tuple:
+        """(anchor_y, (time_x, time_y), (date_x, date_y)): the aligned block anchor and the
+        top-left corners of the time and date canvases, given their (width, height).
+        A hidden line is (0, 0) and takes no space; positions stay inside the margins
+        unless pinned by TIME_POS/DATE_POS.
+        """
+        time_w, time_h = time_size
+        date_w, date_h = date_size
+        time_offset_y, date_offset_y = self._stack_offsets(time_h, date_h)
+        center_y = self._aligned_center_y(center_y, time_offset_y + time_h // 2, date_offset_y + date_h, margin)
+        time_x = max(margin, min(self.fb_width - margin - time_w, self._aligned_x(time_w, center_x_time, margin, self.time_align_h)))
+        time_y = max(margin, min(self.fb_height - margin - time_h, center_y - time_offset_y - (time_h // 2)))
+        date_x = max(margin, min(self.fb_width - margin - date_w, self._aligned_x(date_w, center_x, margin, self.date_align_h)))
+        date_y = max(margin, min(self.fb_height - margin - date_h, center_y + date_offset_y))
+        # TIME_POS/DATE_POS pin a canvas; it is only clamped to stay fully on screen
+        if self.time_pos:
+            time_x = max(0, min(self.fb_width - time_w, self.time_pos[0]))
+            time_y = max(0, min(self.fb_height - time_h, self.time_pos[1]))
+        if self.date_pos:
+            date_x = max(0, min(self.fb_width - date_w, self.date_pos[0]))
+            date_y = max(0, min(self.fb_height - date_h, self.date_pos[1]))
+        return center_y, (time_x, time_y), (date_x, date_y)
+    
+    def _aligned_center_y(self, center_y: int, above: int, below: int, margin: int) -> int:
+        """Vertical anchor of the time/date block under align_v.
+        above/below: how far the block extends above and below the anchor.
+        """
+        shift = abs(center_y - self.fb_height // 2)
+        if self.align_v == 'top':
+            return margin + above + shift
+        if self.align_v == 'bottom':
+            return self.fb_height - margin - below - shift
+        return center_y
+
     def get_display_scale(self) -> float:
         """Compute scale factor based on DISPLAY_RESOLUTION env var.
         If not set or invalid, return 1.0. Scale is capped at 1.0 (no upscaling).
@@ -328,43 +1251,45 @@ class FramebufferClock:
     def init_fonts(self):
         """Initialize TrueType fonts."""
         try:
-            # Try to find Helvetica or similar fonts
-            font_paths = [
-                '/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf',
-                '/usr/share/fonts/truetype/liberation/LiberationSans-Bold.ttf',
-                '/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf',
-            ]
-            
             # Emoji font path
             emoji_font_paths = [
                 '/usr/share/fonts/truetype/noto/NotoColorEmoji.ttf',
                 '/usr/share/fonts/truetype/noto/NotoEmoji-Regular.ttf',
             ]
             
-            font_file = None
-            for path in font_paths:
-                if os.path.exists(path):
-                    font_file = path
-                    break
-            
+            font_file = find_font_file()
             if not font_file:
                 raise Exception("No suitable font found")
             # Keep the chosen font file for dynamic sizing later
             self.font_file = font_file
             
-            self.time_font = ImageFont.truetype(self.font_file, self.time_font_size)
-            self.date_font = ImageFont.truetype(font_file, self.date_font_size)
+            # Optional per-field faces (e.g. bold time, thin date)
+            self.time_font, self.time_font_file = self._load_field_font('TIME_FONT', 'time_font', self.time_font_size)
+            self.date_font, self.date_font_file = self._load_field_font('DATE_FONT', 'date_font', self.date_font_size)
             self.weather_font = ImageFont.truetype(font_file, self.weather_font_size)
             self.status_font = ImageFont.truetype(font_file, self.status_font_size)
-            logging.info(f"Using TrueType font: {font_file}")
+            logging.info(f"Using TrueType font: {font_file} (time: {self.time_font_file}, date: {self.date_font_file})")
         except Exception as e:
             logging.error(f"Failed to load TrueType fonts: {e}")
-            # Fallback to default PIL font
-            self.time_font = ImageFont.load_default()
-            self.date_font = ImageFont.load_default()
-            self.weather_font = ImageFont.load_default()
-            self.status_font = ImageFont.load_default()
-            logging.warning("Using default PIL font")
+            # Fallback to Pillow's embedded font so the clock still starts on minimal images;
+            # an explicit TIME_FONT/DATE_FONT that loads is still used for its field
+            self.font_file = None
+            self.time_font, self.time_font_file = self._load_field_font('TIME_FONT', 'time_font', self.time_font_size)
+            self.date_font, self.date_font_file = self._load_field_font('DATE_FONT', 'date_font', self.date_font_size)
+            self.weather_font = self._builtin_font(self.weather_font_size)
+            self.status_font = self._builtin_font(self.status_font_size)
+            logging.warning("Using built-in PIL font")
+        
+        # Optional fallback face for date glyphs the primary font doesn't cover (CJK, Arabic, ...)
+        self.date_fallback_font = None
+        self._notdef_cache = {}
+        fallback_path = os.environ.get('FALLBACK_FONT', '').strip()
+        if fallback_path:
+            try:
+                self.date_fallback_font = ImageFont.truetype(fallback_path, self.date_font_size)
+                logging.info(f"Fallback font for missing glyphs: {fallback_path}")
+            except Exception as e:
+                logging.warning(f"FALLBACK_FONT={fallback_path} could not be loaded: {e}")
         
         # Simple font cache for dynamic sizing (size -> ImageFont)
         try:
@@ -374,6 +1299,338 @@ class FramebufferClock:
         except Exception:
             self._font_cache = {}
     
+    @staticmethod
+    def _builtin_font(size: int):
+        """Pillow's embedded font: scalable on Pillow >= 10.1, fixed-size bitmap before that."""
+        try:
+            return ImageFont.load_default(size=size)
+        except TypeError:
+            return ImageFont.load_default()
+    
+    def _load_field_font(self, env_var: str, key: str, size: int):
+        """Load the font file named by env_var (else display[key]) for one field, falling back to
+        self.font_file, or Pillow's embedded font when there is none.
+        Returns (ImageFont, path actually used; None for the embedded font).
+        """
+        path, source = os.environ.get(env_var, '').strip(), env_var
+        if not path:
+            path, source = str(self.config.get('display', {}).get(key) or '').strip(), f"config {key}"
+        if path:
+            try:
+                return ImageFont.truetype(path, size), path
+            except Exception as e:
+                logging.warning(f"{source}={path} could not be loaded ({e}), using {self.font_file or 'the built-in font'}")
+        if not self.font_file:
+            return self._builtin_font(size), None
+        return ImageFont.truetype(self.font_file, size), self.font_file
+    
+    def _render_glyph_mask(self, font, char: str):
+        """Render a single glyph to a tight 8-bit mask; returns (size, bytes) for comparison."""
+        left, top, right, bottom = font.getbbox(char)
+        img = Image.new('L', (max(1, right - left), max(1, bottom - top)))
+        ImageDraw.Draw(img).text((-left, -top), char, font=font, fill=255)
+        return img.size, img.tobytes()
+
+    def _is_glyph_missing(self, font, char: str) -> bool:
+        """True if font draws char as its .notdef box (same pixels as an unassigned codepoint)."""
+        try:
+            key = id(font)
+            if key not in self._notdef_cache:
+                self._notdef_cache[key] = self._render_glyph_mask(font, '\U0010FFFD')
+            return self._render_glyph_mask(font, char) == self._notdef_cache[key]
+        except Exception as e:
+            logging.debug(f"Glyph coverage check failed for '{char}': {e}")
+            return False
+
+    def _gamma_correct(self, sprite, color: tuple):
+        """Apply gamma to a sprite rendered in color on black, remapping coverage per channel via LUT."""
+        if self.gamma == 1.0:
+            return sprite
+        lut = []
+        for c in color:
+            lut.extend(round(c * (min(v, c) / c) ** (1.0 / self.gamma)) if c else 0 for v in range(256))
+        return sprite.point(lut)
+
+    def _parse_offset(self, value, name: str):
+        """Parse "dx,dy" (or a [dx, dy] list) into an (int, int) pixel offset; None if unset/invalid."""
+        if value in (None, ''):
+            return None
+        try:
+            parts = value if isinstance(value, (list, tuple)) else str(value).replace(' ', '').split(',')
+            dx, dy = (int(p) for p in parts)
+        except (TypeError, ValueError):
+            logging.warning(f"Invalid {name}={value!r}: expected 'dx,dy' in pixels; ignoring")
+            return None
+        return (dx, dy) if (dx, dy) != (0, 0) else None
+
+    def _parse_position(self, value, name: str):
+        """Parse an absolute "x,y" (or [x, y]) canvas position; None for unset, "auto" or invalid."""
+        if value in (None, '') or str(value).strip().lower() == 'auto':
+            return None
+        try:
+            parts = value if isinstance(value, (list, tuple)) else str(value).replace(' ', '').split(',')
+            x, y = (int(p) for p in parts)
+        except (TypeError, ValueError):
+            logging.warning(f"Invalid {name}={value!r}: expected 'x,y' in pixels or 'auto'; using the aligned layout")
+            return None
+        return x, y
+
+    def _parse_border(self, value, name: str):
+        """Parse "thickness,color" (e.g. "3,#FFFFFF") into (px, RGB); None if unset, 0 or invalid."""
+        if value in (None, ''):
+            return None
+        thickness, _, color = str(value).partition(',')
+        try:
+            thickness = int(thickness)
+            rgb = parse_color(color.strip() or '#FFFFFF')
+        except ValueError as e:
+            logging.warning(f"Invalid {name}={value!r}: expected 'thickness,color' ({e}); no border")
+            return None
+        return (min(50, thickness), rgb) if thickness > 0 else None
+
+    def _draw_rect_outline(self, rect, thickness: int, color: tuple):
+        """Fill the top, bottom, left and right bands of rect (x, y, w, h) in the shadow buffer.
+        The bands lie inside rect, so the next blit or clear of that element erases them too.
+        """
+        x, y, w, h = rect
+        t = min(thickness, w // 2, h // 2)
+        if t <= 0:
+            return
+        native = self._native_color(color)
+        self.fb_shadow[y:y + t, x:x + w] = native
+        self.fb_shadow[y + h - t:y + h, x:x + w] = native
+        self.fb_shadow[y:y + h, x:x + t] = native
+        self.fb_shadow[y:y + h, x + w - t:x + w] = native
+
+    def _parse_text_boxes(self, value) -> list:
+        """Parse TEXT_BOXES: a list (or JSON list) of {text, x, y, size, color} labels.
+        x/y are the label's center as fractions of the screen (default 0.5), size is a font size
+        scaled like the others (default 40), color defaults to DISPLAY_COLOR. Bad entries are skipped.
+        """
+        if not value:
+            return []
+        if isinstance(value, str):
+            try:
+                value = json.loads(value)
+            except ValueError as e:
+                logging.warning(f"Invalid TEXT_BOXES: not a JSON list ({e}); ignoring")
+                return []
+        if not isinstance(value, list):
+            logging.warning(f"Invalid TEXT_BOXES: expected a list of labels, got {type(value).__name__}; ignoring")
+            return []
+        boxes = []
+        for i, entry in enumerate(value):
+            text = str(entry.get('text', '')).strip() if isinstance(entry, dict) else ''
+            if not text:
+                logging.warning(f"TEXT_BOXES[{i}] has no text; skipping")
+                continue
+            try:
+                x = min(1.0, max(0.0, float(entry.get('x', 0.5))))
+                y = min(1.0, max(0.0, float(entry.get('y', 0.5))))
+                size = min(500, max(8, int(entry.get('size', 40))))
+            except (TypeError, ValueError):
+                logging.warning(f"TEXT_BOXES[{i}]: x/y must be fractions and size a number; skipping")
+                continue
+            color = entry.get('color')
+            boxes.append({
+                'text': text, 'x': x, 'y': y,
+                'size': max(8, int(size * self.display_scale)),
+                'color': self.hex_to_rgb(color, fallback=self.color, name=f'TEXT_BOXES[{i}] color') if color else self.color,
+                'rect_attr': f'_last_text_box_rect_{i}',
+                'image': None,  # (color, rendered image) of the last frame
+            })
+        return boxes
+
+    def _render_text_box(self, box: dict, color: tuple) -> Image.Image:
+        """Draw a text box label in color on black with antialiasing padding; reused while the color holds."""
+        if box['image'] and box['image'][0] == color:
+            return box['image'][1]
+        font = ImageFont.truetype(self.font_file, box['size']) if self.font_file else self._builtin_font(box['size'])
+        if not self._temp_draw:
+            self._temp_draw = ImageDraw.Draw(Image.new('RGB', (1, 1)))
+        bb = self._temp_draw.textbbox((0, 0), box['text'], font=font)
+        pad = max(6, int(box['size'] * 0.15))
+        img = Image.new('RGB', (bb[2] - bb[0] + 2 * pad, bb[3] - bb[1] + 2 * pad), (0, 0, 0))
+        ImageDraw.Draw(img).text((pad - bb[0], pad - bb[1]), box['text'], font=font, fill=color)
+        box['image'] = (color, img)
+        return img
+
+    def _read_extra_lines(self):
+        """Reload EXTRA_LINES_FILE into _extra_lines when its mtime changes; erases lines that went away."""
+        try:
+            mtime = os.stat(self.extra_lines_file).st_mtime_ns
+        except OSError:
+            mtime = None
+        if mtime == self._extra_lines_mtime:
+            return
+        self._extra_lines_mtime = mtime
+        texts = []
+        if mtime is not None:
+            try:
+                with open(self.extra_lines_file, encoding='utf-8', errors='replace') as f:
+                    texts = [line.strip() for line in f if line.strip()][:EXTRA_LINES_MAX]
+            except OSError as e:
+                logging.warning(f"Could not read EXTRA_LINES_FILE {self.extra_lines_file}: {e}")
+        for box in self._extra_lines:
+            self._clear_last_rect(box['rect_attr'])
+        self._extra_lines = [{'text': text, 'size': self.extra_lines_size, 'color': self.extra_lines_color,
+                              'rect_attr': f'_last_extra_line_rect_{i}', 'image': None}
+                             for i, text in enumerate(texts)]
+
+    def _parse_gradient(self, value):
+        """Parse GRADIENT "top,bottom" (colors, or a [top, bottom] list) into two RGB tuples; None if unset/invalid."""
+        if not value:
+            return None
+        # Split on commas outside hsv()/hsl() parentheses
+        parts = value if isinstance(value, (list, tuple)) else re.split(r',(?![^(]*\))', str(value))
+        try:
+            top, bottom = (parse_color(part) for part in parts)
+        except ValueError as e:
+            logging.warning(f"Invalid GRADIENT={value!r}: expected 'top,bottom' colors ({e}); using a flat color")
+            return None
+        return top, bottom
+
+    def _text_styled(self) -> bool:
+        """True if sprites need layered styling (shadow, outline, gradient, rainbow) instead of a flat fill."""
+        return bool(self.shadow_offset or self.outline_width or self.gradient or self.rainbow)
+
+    def _glyph_fill(self, color: tuple, char: str, y0: int, rows: int, center: int, size: int) -> np.ndarray:
+        """Fill color for a sprite of char cropped at row y0 of a glyph drawn centered on center.
+        GRADIENT interpolates per row across the same band for every glyph, so digits line up.
+        RAINBOW picks a hue from the character itself (digits evenly around the wheel), so a
+        glyph keeps its color from frame to frame.
+        """
+        if not self.gradient:
+            if self.rainbow:
+                hue = int(char) / 10.0 if char in '0123456789' else (ord(char) * 0.618034) % 1.0
+                rgb = colorsys.hsv_to_rgb(hue, 1.0, max(color) / 255.0)
+                return np.array([c * 255 for c in rgb], dtype=np.float32)
+            return np.array(color, dtype=np.float32)
+        top, bottom = (np.array(c, dtype=np.float32) for c in self.gradient)
+        band_top, band_h = center - 0.4 * size, 0.8 * size
+        t = np.clip((np.arange(y0, y0 + rows, dtype=np.float32) - band_top) / band_h, 0.0, 1.0)
+        return top + (bottom - top) * t[:, None, None]
+
+    def _style_margins(self):
+        """Extra (left, top, right, bottom) px a styled glyph needs beyond its own bbox."""
+        dx, dy = self.shadow_offset or (0, 0)
+        grow = self.outline_width
+        return grow + max(0, -dx), grow + max(0, -dy), grow + max(0, dx), grow + max(0, dy)
+
+    @staticmethod
+    def _shift(coverage: np.ndarray, dx: int, dy: int) -> np.ndarray:
+        """Move a 2D array by (dx, dy), filling vacated pixels with 0."""
+        h, w = coverage.shape
+        out = np.zeros_like(coverage)
+        if abs(dx) < w and abs(dy) < h:
+            out[max(0, dy):h + min(0, dy), max(0, dx):w + min(0, dx)] = \
+                coverage[max(0, -dy):h - max(0, dy), max(0, -dx):w - max(0, dx)]
+        return out
+
+    def _bake_text_style(self, glyph: Image.Image, color: tuple, char: str, bbox, pad: int, center: int, size: int):
+        """Layer drop shadow, outline and fill for a glyph rendered in color on black (once per sprite).
+        The crop is grown by the style margins, past the canvas edge if needed, so nothing clips.
+        Returns (RGB sprite on black, uint8 coverage of all layers, crop x0, crop y0).
+        """
+        left, top, right, bottom = self._style_margins()
+        x0, y0 = bbox[0] - pad - left, bbox[1] - pad - top
+        rgb = np.asarray(glyph.crop((x0, y0, bbox[2] + pad + right, bbox[3] + pad + bottom)), dtype=np.float32)
+        coverage = rgb.max(axis=2) / max(1, max(color))
+        if self.gamma != 1.0:
+            coverage = coverage ** (1.0 / self.gamma)
+        layers = []  # (coverage, color) bottom to top
+        silhouette = coverage
+        if self.outline_width:
+            # Box dilation of the coverage: every pixel takes the max within outline_width
+            mask = Image.fromarray(np.clip(coverage * 255 + 0.5, 0, 255).astype(np.uint8), 'L')
+            silhouette = np.asarray(mask.filter(ImageFilter.MaxFilter(2 * self.outline_width + 1)), dtype=np.float32) / 255.0
+        if self.shadow_offset:
+            layers.append((self._shift(silhouette, *self.shadow_offset), np.array(self.shadow_color, dtype=np.float32)))
+        if self.outline_width:
+            layers.append((silhouette, np.array(self.outline_color, dtype=np.float32)))
+        layers.append((coverage, self._glyph_fill(color, char, y0, coverage.shape[0], center, size)))
+        out = np.zeros(rgb.shape, dtype=np.float32)
+        alpha = np.zeros(coverage.shape, dtype=np.float32)
+        for layer_alpha, layer_color in layers:
+            out = layer_color * layer_alpha[:, :, None] + out * (1.0 - layer_alpha[:, :, None])
+            alpha = layer_alpha + alpha * (1.0 - layer_alpha)
+        sprite = Image.fromarray(np.clip(out + 0.5, 0, 255).astype(np.uint8), 'RGB')
+        return sprite, np.clip(alpha * 255 + 0.5, 0, 255).astype(np.uint8), x0, y0
+
+    def _render_analog_face(self, now, color: tuple) -> Image.Image:
+        """TIME_STYLE=analog: ring, hour ticks, hour and minute hands and (SHOW_SECONDS) a second hand,
+        drawn at twice the size and downsampled so lines are antialiased.
+        """
+        size = max(40, int(min(self.fb_width, self.fb_height) * ANALOG_FACE_FRACTION))
+        scale = 2
+        img = Image.new('RGB', (size * scale, size * scale), (0, 0, 0))
+        draw = ImageDraw.Draw(img)
+        center = size * scale / 2
+        line_w = max(scale, int(size * scale * 0.012))
+        radius = center - line_w
+        
+        def polar(r, angle):
+            # angle in turns clockwise from 12 o'clock
+            return (center + r * math.sin(angle * 2 * math.pi), center - r * math.cos(angle * 2 * math.pi))
+        
+        draw.ellipse((center - radius, center - radius, center + radius, center + radius), outline=color, width=line_w)
+        for hour in range(12):
+            major = hour % 3 == 0
+            draw.line(polar(radius * (0.8 if major else 0.88), hour / 12) + polar(radius - line_w, hour / 12),
+                      fill=color, width=line_w * (2 if major else 1))
+        minutes = now.minute + now.second / 60
+        draw.line((center, center) + polar(radius * 0.5, (now.hour % 12 + minutes / 60) / 12), fill=color, width=line_w * 3)
+        draw.line((center, center) + polar(radius * 0.75, minutes / 60), fill=color, width=line_w * 2)
+        if self.show_seconds:
+            draw.line((center, center) + polar(radius * 0.85, now.second / 60), fill=color, width=max(1, line_w // 2))
+        hub = line_w * 2
+        draw.ellipse((center - hub, center - hub, center + hub, center + hub), fill=color)
+        return img.resize((size, size), Image.LANCZOS)
+    
+    def _draw_segment_char(self, draw, char: str, cx: int, cy: int, size: Optional[int] = None):
+        """Draw a digit or colon as seven-segment shapes centered on (cx, cy).
+        Returns the cell bbox (same for every digit, so '1' keeps its place like a real display).
+        size overrides the time font size (used to measure candidate sizes).
+        """
+        digit_h = (size or self.time_font_size) * 0.72
+        digit_w = digit_h * 0.5
+        t = max(2.0, digit_h * self.segment_thickness)
+        gap = t * 0.15
+        shear = math.tan(math.radians(self.segment_slant))
+
+        def polygon(points):
+            # Lean by shearing x with height above the center line
+            return [(cx + x - y * shear, cy + y) for x, y in points]
+
+        def horizontal(y, x0, x1):
+            x0, x1, h = x0 + gap, x1 - gap, t / 2
+            return polygon([(x0, y), (x0 + h, y - h), (x1 - h, y - h), (x1, y), (x1 - h, y + h), (x0 + h, y + h)])
+
+        def vertical(x, y0, y1):
+            y0, y1, h = y0 + gap, y1 - gap, t / 2
+            return polygon([(x, y0), (x + h, y0 + h), (x + h, y1 - h), (x, y1), (x - h, y1 - h), (x - h, y0 + h)])
+
+        left, right = -digit_w / 2 + t / 2, digit_w / 2 - t / 2
+        top, bottom = -digit_h / 2 + t / 2, digit_h / 2 - t / 2
+        if char == ':':
+            dots = [polygon([(-t / 2, y - t / 2), (t / 2, y - t / 2), (t / 2, y + t / 2), (-t / 2, y + t / 2)])
+                    for y in (-digit_h / 4, digit_h / 4)]
+            for dot in dots:
+                draw.polygon(dot, fill=self.color)
+            cell = dots[0] + dots[1]
+        else:
+            segments = {
+                'a': horizontal(top, left, right), 'g': horizontal(0, left, right), 'd': horizontal(bottom, left, right),
+                'f': vertical(left, top, 0), 'b': vertical(right, top, 0),
+                'e': vertical(left, 0, bottom), 'c': vertical(right, 0, bottom),
+            }
+            for name in SEGMENT_DIGITS[char]:
+                draw.polygon(segments[name], fill=self.color)
+            cell = [point for shape in segments.values() for point in shape]
+        xs = [x for x, _ in cell]
+        ys = [y for _, y in cell]
+        return (int(min(xs)), int(min(ys)), int(math.ceil(max(xs))) + 1, int(math.ceil(max(ys))) + 1)
+
     def _prerender_time_sprites(self):
         """Pre-render time characters as sprites for fast compositing.
         Date sprites are lazy-loaded on first use to reduce startup time.
@@ -385,7 +1642,12 @@ class FramebufferClock:
         t_start = time.time()
         
         # Characters needed for time display (pre-render at startup)
-        chars = '0123456789: AMP'
+        chars = '0123456789: AMP-'
+        if self.time_format:
+            # Every character a custom TIME_FORMAT can produce (day/month names, AM/PM, separators)
+            samples = (datetime(2026, month, day, hour, 58, 58)
+                       for month in range(1, 13) for day in range(1, 8) for hour in (9, 21))
+            chars += ''.join(sorted(set(''.join(now.strftime(self.time_format) for now in samples)) - set(chars)))
         
         logging.info(f"Generating {len(chars)} time sprites at startup...")
         
@@ -418,23 +1680,28 @@ class FramebufferClock:
             
             # Render at center of canvas
             center = large_size // 2
-            temp_draw_img.text((center, center), char, 
-                             font=self.time_font, fill=self.color, anchor='mm')
-            
-            # Find actual pixel bounds
-            bbox = temp_img.getbbox()
+            if self.time_style == 'segment' and (char in SEGMENT_DIGITS or char == ':'):
+                bbox = self._draw_segment_char(temp_draw_img, char, center, center)
+            else:
+                temp_draw_img.text((center, center), char, 
+                                 font=self.time_font, fill=self.color, anchor='mm')
+                # Find actual pixel bounds
+                bbox = temp_img.getbbox()
             if not bbox:
                 logging.warning(f"No pixels rendered for '{char}', skipping")
                 continue
             
             # Crop to actual content + padding for antialiasing
             pad = 8
-            x0 = max(0, bbox[0] - pad)
-            y0 = max(0, bbox[1] - pad)
-            x1 = min(large_size, bbox[2] + pad)
-            y1 = min(large_size, bbox[3] + pad)
-            
-            sprite = temp_img.crop((x0, y0, x1, y1))
+            alpha = None
+            if self._text_styled():
+                sprite, alpha, x0, y0 = self._bake_text_style(temp_img, self.color, char, bbox, pad, center, self.time_font_size)
+            else:
+                x0 = max(0, bbox[0] - pad)
+                y0 = max(0, bbox[1] - pad)
+                x1 = min(large_size, bbox[2] + pad)
+                y1 = min(large_size, bbox[3] + pad)
+                sprite = self._gamma_correct(temp_img.crop((x0, y0, x1, y1)), self.color)
             sprite_w = sprite.width
             sprite_h = sprite.height
             
@@ -452,6 +1719,7 @@ class FramebufferClock:
             self._sprite_cache[char] = {
                 'image': sprite,  # RGB888 for brightness adjustment
                 'rgb565': sprite_rgb565,  # Pre-converted RGB565 for fast blit
+                'alpha': alpha,  # Coverage when a styled fill is baked in
                 'width': sprite_w,
                 'height': sprite_h,
                 'baseline_offset': 0,
@@ -459,26 +1727,50 @@ class FramebufferClock:
                 'font': 'time'
             }
         
+        if self.tabular_digits:
+            self._pad_digit_sprites()
+        
         elapsed = (time.time() - t_start) * 1000
         logging.info(f"✓ Time sprite cache complete: {len(self._sprite_cache)} sprites in {elapsed:.1f}ms")
         logging.info(f"  Date sprites will be lazy-loaded on first use")
     
+    def _pad_digit_sprites(self):
+        """Center each digit sprite in a slot as wide as the widest digit (fixed advance)."""
+        digits = [self._sprite_cache[d] for d in '0123456789' if d in self._sprite_cache]
+        if not digits:
+            return
+        slot = max(info['width'] for info in digits)
+        for info in digits:
+            extra = slot - info['width']
+            if not extra:
+                continue
+            left = extra // 2
+            info['rgb565'] = np.pad(info['rgb565'], ((0, 0), (left, extra - left)))
+            if info.get('alpha') is not None:
+                info['alpha'] = np.pad(info['alpha'], ((0, 0), (left, extra - left)))
+            padded = Image.new('RGB', (slot, info['height']), (0, 0, 0))
+            padded.paste(info['image'], (left, 0))
+            info['image'] = padded
+            info['width'] = slot
+        logging.info(f"Tabular digits: fixed advance of {slot}px")
+    
     def _get_or_create_date_sprite(self, char: str):
         """Lazy-load date sprite on first use.
         Renders sprite on-demand and caches for future use.
         """
-        cache_key = f'date_{char}'
-        
         # Return from cache if already rendered
-        if cache_key in self._sprite_cache:
-            return self._sprite_cache[cache_key]
+        if char in self._date_sprite_cache:
+            self._date_sprite_cache.move_to_end(char)
+            self.sprite_cache_hits += 1
+            return self._date_sprite_cache[char]
+        self.sprite_cache_misses += 1
         
         # Render sprite on-demand
         if char == ' ':
             space_width = int(self.date_font_size * 0.3)
             sprite = Image.new('RGB', (space_width, self.date_font_size), (0, 0, 0))
             sprite_rgb565 = np.zeros((self.date_font_size, space_width), dtype=np.uint16)
-            self._sprite_cache[cache_key] = {
+            return self._cache_date_sprite(char, {
                 'image': sprite,
                 'rgb565': sprite_rgb565,
                 'width': space_width,
@@ -486,8 +1778,7 @@ class FramebufferClock:
                 'baseline_offset': 0,
                 'y_offset': 0,
                 'font': 'date'
-            }
-            return self._sprite_cache[cache_key]
+            })
         
         # Render on large canvas
         large_size = int(self.date_font_size * 4)
@@ -495,15 +1786,19 @@ class FramebufferClock:
         temp_draw_img = ImageDraw.Draw(temp_img)
         
         center = large_size // 2
+        font = self.date_font
+        if self.date_fallback_font and self._is_glyph_missing(font, char):
+            logging.debug(f"Glyph '{char}' missing from date font, using fallback font")
+            font = self.date_fallback_font
         temp_draw_img.text((center, center), char,
-                         font=self.date_font, fill=self.color, anchor='mm')
+                         font=font, fill=self.date_color, anchor='mm')
         
         bbox = temp_img.getbbox()
         if not bbox:
             # Return empty sprite if char didn't render
             sprite = Image.new('RGB', (1, self.date_font_size), (0, 0, 0))
             sprite_rgb565 = np.zeros((self.date_font_size, 1), dtype=np.uint16)
-            self._sprite_cache[cache_key] = {
+            return self._cache_date_sprite(char, {
                 'image': sprite,
                 'rgb565': sprite_rgb565,
                 'width': 1,
@@ -511,16 +1806,18 @@ class FramebufferClock:
                 'baseline_offset': 0,
                 'y_offset': 0,
                 'font': 'date'
-            }
-            return self._sprite_cache[cache_key]
+            })
         
         pad = 5
-        x0 = max(0, bbox[0] - pad)
-        y0 = max(0, bbox[1] - pad)
-        x1 = min(large_size, bbox[2] + pad)
-        y1 = min(large_size, bbox[3] + pad)
-        
-        sprite = temp_img.crop((x0, y0, x1, y1))
+        alpha = None
+        if self._text_styled():
+            sprite, alpha, x0, y0 = self._bake_text_style(temp_img, self.date_color, char, bbox, pad, center, self.date_font_size)
+        else:
+            x0 = max(0, bbox[0] - pad)
+            y0 = max(0, bbox[1] - pad)
+            x1 = min(large_size, bbox[2] + pad)
+            y1 = min(large_size, bbox[3] + pad)
+            sprite = self._gamma_correct(temp_img.crop((x0, y0, x1, y1)), self.date_color)
         sprite_w = sprite.width
         sprite_h = sprite.height
         y_offset_from_center = y0 - center
@@ -533,21 +1830,213 @@ class FramebufferClock:
         sprite_rgb565 = ((r << 11) | (g << 5) | b)
         
         # Cache for future use
-        self._sprite_cache[cache_key] = {
+        return self._cache_date_sprite(char, {
             'image': sprite,
             'rgb565': sprite_rgb565,
+            'alpha': alpha,
             'width': sprite_w,
             'height': sprite_h,
             'baseline_offset': 0,
             'y_offset': y_offset_from_center,
             'font': 'date'
-        }
-        
-        return self._sprite_cache[cache_key]
+        })
+
+    def _cache_date_sprite(self, char: str, sprite_info: dict) -> dict:
+        """Store a date sprite, evicting the least recently used one past DATE_SPRITE_CACHE_MAX."""
+        self._date_sprite_cache[char] = sprite_info
+        if len(self._date_sprite_cache) > DATE_SPRITE_CACHE_MAX:
+            self._date_sprite_cache.popitem(last=False)
+        return sprite_info
+
+    def _use_time_sprites(self):
+        """Switch time sprites (and their fixed canvas width) to the current time font size.
+        Sets are rendered on first use and kept in an LRU of TIME_SPRITE_SIZES_MAX sizes.
+        """
+        if self._time_sprite_set is not None:
+            self._time_sprite_set['canvas_width'] = getattr(self, '_time_canvas_width', None)
+        size = self.time_font_size
+        entry = self._time_sprite_sets.get(size)
+        if entry is None:
+            self._sprite_cache = {}
+            self._prerender_time_sprites()
+            entry = {'sprites': self._sprite_cache, 'canvas_width': None}
+            self._time_sprite_sets[size] = entry
+            if len(self._time_sprite_sets) > TIME_SPRITE_SIZES_MAX:
+                self._time_sprite_sets.popitem(last=False)
+        else:
+            self._time_sprite_sets.move_to_end(size)
+            self._sprite_cache = entry['sprites']
+            logging.debug(f"Reusing cached time sprites at {size}px")
+        self._time_sprite_set = entry
+        if entry['canvas_width'] is not None:
+            self._time_canvas_width = entry['canvas_width']
+        elif hasattr(self, '_time_canvas_width'):
+            del self._time_canvas_width
+    
+    def _seconds_span(self, time_str: str) -> range:
+        """Indices of the ":SS" drawn at SECONDS_SIZE: the second colon group of a built-in time format."""
+        if not self.seconds_font_size or self.time_format or not self.show_seconds:
+            return range(0)
+        first = time_str.find(':')
+        second = time_str.find(':', first + 1) if first >= 0 else -1
+        return range(second, second + 3) if second >= 0 else range(0)
+    
+    def _seconds_sprites(self) -> dict:
+        """Digit and colon sprites for the ":SS" group, downscaled from the active time set to SECONDS_SIZE.
+        Their bottoms line up with the full-size digits' so the seconds sit on the same baseline.
+        Kept with the time sprite set, so font size changes rebuild them.
+        """
+        entry = self._time_sprite_set
+        factor = min(1.0, self.seconds_font_size / max(1, self.time_font_size))
+        cached = entry.get('seconds') if entry else None
+        if cached and cached[0] == factor:
+            return cached[1]
+        sprites = {}
+        base = self._sprite_cache.get('0')
+        for char in '0123456789:':
+            info = self._sprite_cache.get(char)
+            if info is None:
+                continue
+            w = max(1, round(info['width'] * factor))
+            h = max(1, round(info['height'] * factor))
+            image = info['image'].resize((w, h), Image.LANCZOS)
+            arr = np.frombuffer(image.tobytes(), dtype=np.uint8).reshape((h, w, 3))
+            alpha = info.get('alpha')
+            if alpha is not None:
+                alpha = np.asarray(Image.fromarray(alpha).resize((w, h), Image.LANCZOS))
+            sprites[char] = dict(info, image=image, rgb565=self._rgb888_to_rgb565(arr), alpha=alpha,
+                                 width=w, height=h, y_offset=round(info['y_offset'] * factor))
+        if base is not None and '0' in sprites:
+            # Shift the whole group so its digit bottoms meet the full-size digit bottoms
+            small = sprites['0']
+            shift = (base['y_offset'] + base['height']) - (small['y_offset'] + small['height'])
+            for info in sprites.values():
+                info['y_offset'] += shift
+        if entry is not None:
+            entry['seconds'] = (factor, sprites)
+        return sprites
+    
+    def _time_text_width(self, text: str, size: int) -> int:
+        """Width of text as composited from time sprites at the given font size.
+        Mirrors _prerender_time_sprites: glyph bbox plus 8px crop padding per side, spaces 30%.
+        """
+        left, _, right, _ = self._style_margins() if self._text_styled() else (0, 0, 0, 0)
+        extra = left + right
+        font = self.time_font.font_variant(size=size)
+        measure = getattr(self, '_temp_draw', None) or ImageDraw.Draw(Image.new('RGB', (1, 1)))
+        width = 0
+        for char in text:
+            if char == ' ':
+                width += int(size * 0.3)
+                continue
+            if self.time_style == 'segment' and (char in SEGMENT_DIGITS or char == ':'):
+                bbox = self._draw_segment_char(measure, char, 0, 0, size)
+            else:
+                bbox = font.getbbox(char)
+            width += (bbox[2] - bbox[0]) + 16 + extra
+        return width
+
+    def _auto_time_size(self, time_str: str) -> int:
+        """SIZE_AUTO_TIME: largest time font size whose widest rendering of time_str fits in
+        size_auto_time% of the screen width. Binary search, cached per digit pattern so it only
+        reruns when the time layout changes (e.g. seconds toggled), not every minute.
+        """
+        if self._widest_time_digit is None:
+            self._widest_time_digit = max('0123456789', key=lambda d: self._time_text_width(d, 100))
+        # Every digit at its widest, hours padded to two digits, so 9:59 -> 10:00 doesn't resize
+        pattern = re.sub(r'\d+', lambda m: self._widest_time_digit * max(2, len(m.group())), time_str)
+        key = (pattern, self.fb_width)
+        if key not in self._size_auto_cache:
+            target = self.fb_width * self.size_auto_time / 100
+            low, high = 10, 1000
+            while low < high:
+                mid = (low + high + 1) // 2
+                if self._time_text_width(pattern, mid) <= target:
+                    low = mid
+                else:
+                    high = mid - 1
+            self._size_auto_cache[key] = low
+            logging.info(f"SIZE_AUTO_TIME={self.size_auto_time}%: time font {low}px fits '{pattern}' in {int(target)}px")
+        return self._size_auto_cache[key]
+
+    def _apply_size_auto_time(self, time_str: str):
+        """Switch the time font to the SIZE_AUTO_TIME size for time_str (no-op when unchanged).
+        Burn-in variation is re-centered on it and capped so it never overflows the target.
+        """
+        if not getattr(self, 'time_font_file', None):
+            logging.warning("SIZE_AUTO_TIME needs a TrueType time font; using TIME_FONT_SIZE")
+            self.size_auto_time = 0
+            return
+        size = self._auto_time_size(time_str)
+        if size == self._size_auto_applied:
+            return
+        self._size_auto_applied = size
+        self.base_time_font_size = max(10, int(size / self.display_scale))
+        self._time_size_cap = size
+        if size != self.time_font_size:
+            self.time_font = self.time_font.font_variant(size=size)
+            self.time_font_size = size
+            if hasattr(self, '_time_sprite_sets'):
+                self._use_time_sprites()
+
+    def _fit_time_font(self, time_str: str, avail: int):
+        """AUTO_SHRINK_TIME: shrink the time font until time_str fits in avail px.
+        The shrunken size caps later burn-in size variation so it isn't undone.
+        """
+        if not getattr(self, 'time_font_file', None):
+            return
+        for _ in range(3):
+            width = sum(self._sprite_cache[c]['width'] for c in time_str if c in self._sprite_cache)
+            if width <= avail or self.time_font_size <= 10:
+                return
+            size = max(10, int(self.time_font_size * avail / width))
+            logging.info(f"Time '{time_str}' is {width}px wide (> {avail}px), shrinking font to {size}px")
+            self.time_font = ImageFont.truetype(self.time_font_file, size)
+            self.time_font_size = size
+            self._time_size_cap = size
+            self._use_time_sprites()
+    
+    def _set_date_font_size(self, size: int):
+        """Switch the date (and fallback) font to size and drop sprites rendered at the old size."""
+        if size == self.date_font_size:
+            return
+        self.date_font = self.date_font.font_variant(size=size)
+        if self.date_fallback_font:
+            self.date_fallback_font = self.date_fallback_font.font_variant(size=size)
+        self.date_font_size = size
+        self._date_sprite_cache.clear()
+        self._notdef_cache.clear()
+        if hasattr(self, '_date_canvas_width'):
+            del self._date_canvas_width
+    
+    def _fit_date_font(self, date_str: str, avail: int):
+        """AUTO_FIT_DATE: use the largest date size (up to the configured one) at which date_str fits.
+        Cached per (text, requested size, fb width), so this only re-measures when the date changes.
+        """
+        if not getattr(self, 'date_font_file', None):
+            return
+        key = (date_str, self._requested_date_font_size, self.fb_width)
+        size = self._date_fit_cache.get(key)
+        if size is None:
+            size = self._requested_date_font_size
+            self._set_date_font_size(size)
+            for _ in range(4):
+                width = sum(self._get_or_create_date_sprite(c)['width'] for c in date_str)
+                if width <= avail or size <= 8:
+                    break
+                size = max(8, int(size * avail / width))
+                self._set_date_font_size(size)
+            if size != self._requested_date_font_size:
+                logging.info(f"Date '{date_str}' shrunk to {size}px to fit {avail}px")
+            if len(self._date_fit_cache) > 64:
+                self._date_fit_cache.clear()
+            self._date_fit_cache[key] = size
+        self._set_date_font_size(size)
     
     def _composite_time_from_cache(self, time_str: str, color: tuple):
         """Composite time string from pre-rendered sprite cache.
-        Returns (rgb565_array, width, height) tuple for ultra-fast blitting.
+        Returns (rgb565_array, width, height, alpha) tuple for ultra-fast blitting; alpha is the
+        uint8 coverage of styled (e.g. shadowed) sprites, else None.
         RGB565 conversion already done during cache creation.
         """
         if not time_str or not self._sprite_cache:
@@ -558,12 +2047,26 @@ class FramebufferClock:
         total_width = 0
         max_height = 0
         sprites_to_use = []
+        seconds = self._seconds_span(time_str)
+        seconds_sprites = self._seconds_sprites() if seconds else {}
         
-        for char in time_str:
+        for i, char in enumerate(time_str):
             if char not in self._sprite_cache:
+                self.sprite_cache_misses += 1
                 logging.warning(f"Sprite cache MISS for char='{char}' (ord={ord(char)}, time_str='{time_str}')")
                 return None
-            sprite_info = self._sprite_cache[char]
+            self.sprite_cache_hits += 1
+            sprite_info = seconds_sprites.get(char) if i in seconds else None
+            sprite_info = sprite_info or self._sprite_cache[char]
+            if char == ':' and not self._colon_visible:
+                # Keep the colon's advance width so the digits don't jitter
+                sprite_info = dict(sprite_info, rgb565=np.zeros_like(sprite_info['rgb565']), alpha=None)
+            elif i == 0 and char == '0' and self.leading_zero != 'show' and time_str[1:2].isdigit():
+                # Leading hour zero keeps its advance, so the layout doesn't move at 10:00
+                factor = 0.0 if self.leading_zero == 'hide' else self.leading_zero_dim
+                alpha = sprite_info.get('alpha')
+                sprite_info = dict(sprite_info, rgb565=self._scale_rgb565(sprite_info['rgb565'], factor),
+                                   alpha=(alpha * factor).astype(np.uint8) if alpha is not None else None)
             sprites_to_use.append(sprite_info)
             total_width += sprite_info['width']
             max_height = max(max_height, sprite_info['height'])
@@ -582,8 +2085,12 @@ class FramebufferClock:
         # Use FIXED canvas width
         if not hasattr(self, '_time_canvas_width'):
             max_width = 0
-            for char in "10:00:00 PM":
-                if char in self._sprite_cache:
+            sample = datetime(2026, 9, 30, 22, 58, 58).strftime(self.time_format) if self.time_format else "10:00:00 PM"
+            sample_seconds = self._seconds_span(sample)
+            for i, char in enumerate(sample):
+                if i in sample_seconds and char in seconds_sprites:
+                    max_width += seconds_sprites[char]['width']
+                elif char in self._sprite_cache:
                     max_width += self._sprite_cache[char]['width']
             self._time_canvas_width = max_width
         
@@ -594,6 +2101,9 @@ class FramebufferClock:
             logging.warning(f"Time string too wide ({total_width}px) for canvas ({canvas_width}px), expanding canvas")
             canvas_width = total_width
             self._time_canvas_width = canvas_width
+        if self.auto_shrink_time and self._fit_width:
+            # Don't let the fixed-width padding push fitted text off-center
+            canvas_width = max(total_width, min(canvas_width, self._fit_width))
         
         x_start = (canvas_width - total_width) // 2
         
@@ -603,10 +2113,14 @@ class FramebufferClock:
         
         # Create RGB565 canvas directly (no PIL Image intermediate)
         canvas_rgb565 = np.zeros((canvas_height, canvas_width), dtype=np.uint16)
+        canvas_alpha = np.zeros((canvas_height, canvas_width), dtype=np.uint8) if self._text_styled() else None
         
-        # Check if we need brightness adjustment
+        # Check if we need brightness adjustment (sprites were rendered in self.color)
         needs_tint = color != self.color
-        brightness_factor = color[0] / max(1, self.color[0]) if self.color[0] > 0 else 1.0
+        brightness_factor = max(color) / max(1, max(self.color))
+        # A different hue (e.g. NIGHT_COLOR) can't be reached by scaling; recolor by coverage.
+        # Styled sprites are multi-colored, so they are only dimmed
+        recolor = needs_tint and not self._same_hue(color, self.color) and not self._text_styled()
         
         # Blit each sprite (use pre-converted RGB565 data)
         x_offset = x_start
@@ -619,15 +2133,10 @@ class FramebufferClock:
             sprite_data = sprite_info['rgb565']
             
             # Apply brightness if needed (adjust RGB565 values)
-            if needs_tint and brightness_factor != 1.0:
-                # Extract RGB components, apply brightness, recombine
-                r = ((sprite_data >> 11) & 0x1F)
-                g = ((sprite_data >> 5) & 0x3F)
-                b = (sprite_data & 0x1F)
-                r = (r * brightness_factor).astype(np.uint16).clip(0, 31)
-                g = (g * brightness_factor).astype(np.uint16).clip(0, 63)
-                b = (b * brightness_factor).astype(np.uint16).clip(0, 31)
-                sprite_data = (r << 11) | (g << 5) | b
+            if recolor:
+                sprite_data = self._recolor_rgb565(sprite_data, color, self.color)
+            elif needs_tint and brightness_factor != 1.0:
+                sprite_data = self._scale_rgb565(sprite_data, brightness_factor)
             
             # Bounds check: ensure sprite fits within canvas
             if x_offset + sw > canvas_width:
@@ -639,6 +2148,7 @@ class FramebufferClock:
                 logging.debug(f"Clipping sprite from {sw}px to {sw_clipped}px")
                 sprite_data = sprite_data[:, :sw_clipped]
                 sw = sw_clipped
+            sprite_alpha = sprite_info.get('alpha')
             
             # Additional safety checks
             if y_off < 0 or y_off + sh > canvas_height:
@@ -652,6 +2162,8 @@ class FramebufferClock:
             # Blit into canvas
             try:
                 canvas_rgb565[y_off:y_off+sh, x_offset:x_offset+sw] = sprite_data
+                if canvas_alpha is not None and sprite_alpha is not None:
+                    canvas_alpha[y_off:y_off+sh, x_offset:x_offset+sw] = sprite_alpha[:, :sw]
             except ValueError as e:
                 logging.error(f"Blit failed: canvas shape={canvas_rgb565.shape}, "
                             f"sprite shape={sprite_data.shape}, "
@@ -660,11 +2172,11 @@ class FramebufferClock:
                 raise
             x_offset += sw
         
-        return (canvas_rgb565, canvas_width, canvas_height)
+        return (canvas_rgb565, canvas_width, canvas_height, canvas_alpha)
     
     def _composite_date_from_cache(self, date_str: str, color: tuple):
         """Composite date string from pre-rendered sprite cache.
-        Returns (rgb565_array, width, height) tuple for ultra-fast blitting.
+        Returns (rgb565_array, width, height, alpha) tuple for ultra-fast blitting.
         """
         if not date_str or not self._sprite_cache:
             return None
@@ -709,6 +2221,9 @@ class FramebufferClock:
             logging.warning(f"Date string too wide ({total_width}px) for canvas ({canvas_width}px), expanding canvas")
             canvas_width = total_width
             self._date_canvas_width = canvas_width
+        if self.auto_fit_date and self._fit_width:
+            # Don't let the fixed-width padding push fitted text off-center
+            canvas_width = max(total_width, min(canvas_width, self._fit_width))
         
         x_start = (canvas_width - total_width) // 2
         
@@ -718,10 +2233,14 @@ class FramebufferClock:
         
         # Create RGB565 canvas directly
         canvas_rgb565 = np.zeros((canvas_height, canvas_width), dtype=np.uint16)
+        canvas_alpha = np.zeros((canvas_height, canvas_width), dtype=np.uint8) if self._text_styled() else None
         
-        # Apply brightness if needed
-        needs_tint = color != self.color
-        brightness_factor = color[0] / max(1, self.color[0]) if self.color[0] > 0 else 1.0
+        # Apply brightness if needed (sprites were rendered in self.date_color)
+        needs_tint = color != self.date_color
+        brightness_factor = max(color) / max(1, max(self.date_color))
+        # A different hue (e.g. NIGHT_COLOR) can't be reached by scaling; recolor by coverage.
+        # Styled sprites are multi-colored, so they are only dimmed
+        recolor = needs_tint and not self._same_hue(color, self.date_color) and not self._text_styled()
         
         x_offset = x_start
         for sprite_info in sprites_to_use:
@@ -733,7 +2252,9 @@ class FramebufferClock:
             sprite_data = sprite_info['rgb565']
             
             # Apply brightness if needed
-            if needs_tint and brightness_factor != 1.0:
+            if recolor:
+                sprite_data = self._recolor_rgb565(sprite_data, color, self.date_color)
+            elif needs_tint and brightness_factor != 1.0:
                 r = ((sprite_data >> 11) & 0x1F)
                 g = ((sprite_data >> 5) & 0x3F)
                 b = (sprite_data & 0x1F)
@@ -751,6 +2272,7 @@ class FramebufferClock:
                     break  # No room left
                 sprite_data = sprite_data[:, :sw_clipped]
                 sw = sw_clipped
+            sprite_alpha = sprite_info.get('alpha')
             
             # Additional safety checks
             if y_off < 0 or y_off + sh > canvas_height:
@@ -764,6 +2286,8 @@ class FramebufferClock:
             # Blit into canvas
             try:
                 canvas_rgb565[y_off:y_off+sh, x_offset:x_offset+sw] = sprite_data
+                if canvas_alpha is not None and sprite_alpha is not None:
+                    canvas_alpha[y_off:y_off+sh, x_offset:x_offset+sw] = sprite_alpha[:, :sw]
             except ValueError as e:
                 logging.error(f"Date blit failed: canvas shape={canvas_rgb565.shape}, "
                             f"sprite shape={sprite_data.shape}, "
@@ -772,12 +2296,17 @@ class FramebufferClock:
                 raise
             x_offset += sw
         
-        return (canvas_rgb565, canvas_width, canvas_height)
+        return (canvas_rgb565, canvas_width, canvas_height, canvas_alpha)
 
-    def hex_to_rgb(self, hex_color):
-        """Convert hex color to RGB tuple."""
-        hex_color = hex_color.lstrip('#')
-        return tuple(int(hex_color[i:i+2], 16) for i in (0, 2, 4))
+    def hex_to_rgb(self, hex_color, fallback=(0, 255, 0), name=None):
+        """Convert a configured color to an RGB tuple, warning and using fallback if invalid.
+        name (e.g. 'DATE_COLOR') is included in the warning to identify the bad setting.
+        """
+        try:
+            return parse_color(hex_color)
+        except ValueError as e:
+            logging.warning(f"Invalid {name or 'color'} setting: {e}; using {fallback}")
+            return fallback
 
     def _draw_icon(self, draw, x, y, icon_type, color):
         """Draw a tiny bitmap icon (10x10) for status items."""
@@ -820,7 +2349,7 @@ class FramebufferClock:
             if hasattr(self, '_status_cached_img') and hasattr(self, '_status_cached_pos'):
                 status_img = self._status_cached_img
                 status_x, status_y = self._status_cached_pos
-                self.blit_rgb_image(status_img, status_x, status_y, clear_last_rect_attr='_last_status_rect', skip_write=True)
+                self.blit_rgb_image(status_img, status_x, status_y, clear_last_rect_attr='_last_status_rect', skip_write=True, text_color=status_color)
                 logging.info(f"Status cache HIT (min={current_minute})")
                 return
         
@@ -891,7 +2420,7 @@ class FramebufferClock:
                 sep = self._temp_draw.textbbox((0,0), " | ", font=self.status_font)
                 cursor_rel_x += sep[2] - sep[0]
         
-        self.blit_rgb_image(status_img, status_x, status_y, clear_last_rect_attr='_last_status_rect', skip_write=True)
+        self.blit_rgb_image(status_img, status_x, status_y, clear_last_rect_attr='_last_status_rect', skip_write=True, text_color=status_color)
         
         # Cache the rendered status bar with minute timestamp
         self._status_last_minute = current_minute
@@ -1024,7 +2553,7 @@ class FramebufferClock:
         cy1 = self.fb_height - panel_margin - 20
         d.rectangle([cx0, cy0, cx1, cy1], outline=(60,60,60))
         self._render_tab_content(d, cx0+20, cy0+20, cx1-20, cy1-20)
-        self.blit_rgb_image(overlay, 0, 0)
+        self.blit_rgb_image(overlay, 0, 0, clear_last_rect_attr='_last_overlay_rect')
 
     def _set_active_tab(self, t):
         self.active_settings_tab = t
@@ -1090,8 +2619,49 @@ class FramebufferClock:
             except Exception:
                 d.text((x0, y), "Version info unavailable", font=self.status_font, fill=(160,160,160))
     
+    @staticmethod
+    def _parse_countdown(value) -> Optional[datetime]:
+        """COUNTDOWN_TO as a local datetime: "HH:MM[:SS]" means its next occurrence, anything with
+        a date is read as ISO 8601 (an explicit UTC offset is converted). None if unset or invalid.
+        """
+        if not value:
+            return None
+        text = str(value).strip()
+        now = datetime.now()
+        try:
+            if '-' in text or 'T' in text:
+                target = datetime.fromisoformat(text)
+                if target.tzinfo:
+                    target = target.astimezone().replace(tzinfo=None)
+                return target
+            parts = [int(p) for p in text.split(':')]
+            if len(parts) not in (2, 3):
+                raise ValueError('expected HH:MM or HH:MM:SS')
+            target = now.replace(hour=parts[0], minute=parts[1], second=parts[2] if len(parts) == 3 else 0, microsecond=0)
+        except ValueError as e:
+            logging.warning(f"Invalid COUNTDOWN_TO={value!r}: {e}; showing the clock")
+            return None
+        return target if target > now else target + timedelta(days=1)
+    
+    def _countdown_phase(self, now) -> Optional[str]:
+        """'running' before COUNTDOWN_TO, 'alarm' for countdown_alarm_seconds after it, else None."""
+        if not self.countdown_to:
+            return None
+        if now < self.countdown_to:
+            return 'running'
+        if now < self.countdown_to + timedelta(seconds=self.countdown_alarm_seconds):
+            return 'alarm'
+        return None
+    
     def format_time(self, now):
         """Format time string."""
+        if self._countdown_phase(now):
+            remaining = max(0, math.ceil((self.countdown_to - now).total_seconds()))
+            hours, rest = divmod(remaining, 3600)
+            minutes, seconds = divmod(rest, 60)
+            return f"{hours}:{minutes:02d}:{seconds:02d}" if hours else f"{minutes:02d}:{seconds:02d}"
+        if self.time_format:
+            return now.strftime(self.time_format)
         if self.format_12h:
             if self.show_seconds:
                 # Prefer Linux-specific %-I to suppress leading zero; fallback if unsupported
@@ -1114,8 +2684,30 @@ class FramebufferClock:
     
     def format_date(self, now):
         """Format date string."""
-        date_format = self.config.get('display', {}).get('date_format', "%A, %B %d, %Y")
-        return now.strftime(date_format)
+        return now.strftime(self.date_format)
+    
+    def _blink_rates(self, now_ts: float) -> tuple:
+        """(time Hz, date Hz): BLINK_TIME/BLINK_DATE, or STROBE_HZ for both once a countdown ends."""
+        if self._countdown_phase(datetime.fromtimestamp(now_ts)) == 'alarm':
+            return self.strobe_hz, self.strobe_hz
+        return self.blink_time_hz, self.blink_date_hz
+    
+    def _blink_state(self, now_ts: float) -> tuple:
+        """(time on, date on) for BLINK_TIME/BLINK_DATE at now_ts; like the colon, steady while fully
+        dimmed (the countdown alarm flashes regardless).
+        """
+        if self.current_brightness <= 0 and self._countdown_phase(datetime.fromtimestamp(now_ts)) != 'alarm':
+            return True, True
+        return tuple(not hz or int(now_ts * hz * 2) % 2 == 0 for hz in self._blink_rates(now_ts))
+    
+    def _until_blink_toggle(self, delay: float) -> float:
+        """Shorten a loop sleep of delay seconds so it ends at the next BLINK_TIME/BLINK_DATE toggle."""
+        now_ts = time.time()
+        for hz in self._blink_rates(now_ts):
+            if hz:
+                toggle_at = (math.floor(now_ts * hz * 2) + 1) / (hz * 2)
+                delay = min(delay, max(0.01, toggle_at - now_ts))
+        return delay
     
     def is_in_time_window(self, current_hour, start_hour, end_hour):
         """Check if current hour is within a time window."""
@@ -1133,18 +2725,76 @@ class FramebufferClock:
         return not in_screensaver_window
     
     def update_brightness(self):
-        """Update brightness based on time of day."""
-        if not self.dim_at_night:
-            self.current_brightness = 1.0
-            return
-        current_hour = datetime.now().hour
-        if self.is_in_time_window(current_hour, self.night_start, self.night_end):
-            self.current_brightness = self.night_brightness
+        """Update brightness (and night color blend) based on time of day.
+        With night_fade_seconds set, the day/night level moves gradually over successive renders.
+        """
+        target = 0.0
+        if self.dim_at_night and self.is_in_time_window(datetime.now().hour, self.night_start, self.night_end):
+            target = 1.0
+        now = time.monotonic()
+        if self._night_level_updated is None or self.night_fade_seconds <= 0:
+            self._night_level = target
         else:
-            self.current_brightness = 1.0
+            step = (now - self._night_level_updated) / self.night_fade_seconds
+            if self._night_level < target:
+                self._night_level = min(target, self._night_level + step)
+            else:
+                self._night_level = max(target, self._night_level - step)
+        self._night_level_updated = now
+        self.current_brightness = 1.0 + (self.night_brightness - 1.0) * self._night_level
+        if self.light_sensor and self.light_sensor.lux is not None:
+            level = light_sensor.brightness_for_lux(self.autobright_curve, self.light_sensor.lux)
+            # Ignore small changes so brightness doesn't flicker around a curve point
+            if self._autobright_level is None or abs(level - self._autobright_level) >= self.autobright_hysteresis:
+                self._autobright_level = level
+        if self._autobright_level is not None:
+            self.current_brightness = self._autobright_level
+        if self.brightness_mode != 'sw':
+            self.set_backlight(round(self.backlight_min + (self.backlight_max - self.backlight_min) * self.current_brightness))
+    
+    def _read_backlight(self, name: str) -> Optional[int]:
+        """Read an integer attribute (brightness, max_brightness) of BACKLIGHT_DEVICE."""
+        try:
+            with open(os.path.join(self.backlight_device, name)) as f:
+                return int(f.read().strip())
+        except (OSError, ValueError) as e:
+            logging.warning(f"BACKLIGHT_DEVICE={self.backlight_device}: can't read {name} ({e})")
+            return None
+    
+    def set_backlight(self, value: int):
+        """Write the hardware backlight level (only when it changes).
+        On permission errors, hardware dimming is switched off rather than retried every frame.
+        """
+        if value == self._backlight_value:
+            return
+        try:
+            # sysfs attributes can't be replaced by rename; a single write of the whole value is atomic
+            with open(os.path.join(self.backlight_device, 'brightness'), 'w') as f:
+                f.write(f"{value}\n")
+            self._backlight_value = value
+        except PermissionError:
+            logging.error(f"No permission to write {self.backlight_device}/brightness; add a udev rule "
+                          f"(e.g. SUBSYSTEM==\"backlight\", RUN+=\"/bin/chmod 666 /sys/class/backlight/%k/brightness\") "
+                          f"or run with access to it. Falling back to software dimming.")
+            self.brightness_mode = 'sw'
+        except OSError as e:
+            logging.warning(f"Failed to set backlight to {value}: {e}")
+    
+    def apply_night_color(self, color):
+        """Blend a day color towards night_color, then towards night_color_temp, by the current night level."""
+        t = self._night_level
+        if t <= 0:
+            return color
+        if self.night_color:
+            color = tuple(int(round(c + (n - c) * t)) for c, n in zip(color, self.night_color))
+        if self._night_temp_factors:
+            color = tuple(int(round(c * (1.0 + (f - 1.0) * t))) for c, f in zip(color, self._night_temp_factors))
+        return color
     
     def apply_brightness(self, color):
-        """Apply current brightness to a color tuple."""
+        """Apply current brightness to a color tuple (left to the backlight in hw mode)."""
+        if self.brightness_mode == 'hw':
+            return color
         return tuple(int(c * self.current_brightness) for c in color)
 
     def check_network_status(self):
@@ -1230,13 +2880,17 @@ class FramebufferClock:
         now = time.time()
         # Apply pixel shift only at minute boundary to avoid visible tearing
         if now - self.last_pixel_shift > self.pixel_shift_interval and datetime.now().second == 0:
-            # Lazy import random only when needed
-            import random
-            self.pixel_shift_x = random.randint(-self.pixel_shift_max, self.pixel_shift_max)
-            self.pixel_shift_y = random.randint(-self.pixel_shift_max, self.pixel_shift_max)
+            self._pixel_shift_step += 1
+            self.pixel_shift_x, self.pixel_shift_y = self._pixel_shift_position(self._pixel_shift_step)
             self.last_pixel_shift = now
             logging.debug(f"Pixel shift applied: x={self.pixel_shift_x:+d}, y={self.pixel_shift_y:+d}")
     
+    def _pixel_shift_position(self, step: int):
+        """Offset for the given walk step; covers every position within ±pixel_shift_max."""
+        side = 2 * self.pixel_shift_max + 1
+        cell = (step * self._pixel_shift_stride) % (side * side)
+        return cell % side - self.pixel_shift_max, cell // side - self.pixel_shift_max
+    
     def update_burn_in_protection(self):
         """Vary font size and characteristics to prevent burn-in."""
         if not self.pixel_shift_enabled:  # Reuse same enable flag
@@ -1250,18 +2904,24 @@ class FramebufferClock:
         
         if now - self._last_font_variation > 300 and datetime.now().second == 0:  # 5 min
             import random
-            # Vary time font size by ±8% (e.g., 280 ± 22px)
+            # Vary time font size by ±8% (e.g., 280 ± 22px) in TIME_SPRITE_SIZES_MAX steps,
+            # so every size stays in the sprite LRU after its first render
             base_size = self.base_time_font_size
             variation = int(base_size * 0.08)
-            self._font_size_offset = random.randint(-variation, variation)
+            steps = TIME_SPRITE_SIZES_MAX - 1
+            self._font_size_offset = -variation + (2 * variation * random.randint(0, steps)) // steps
             new_size = max(10, int((base_size + self._font_size_offset) * self.display_scale))
+            if self._time_size_cap:
+                new_size = min(new_size, self._time_size_cap)
             
             try:
-                if getattr(self, 'font_file', None):
-                    self.time_font = ImageFont.truetype(self.font_file, new_size)
+                if getattr(self, 'time_font_file', None):
+                    self.time_font = ImageFont.truetype(self.time_font_file, new_size)
                     self.time_font_size = new_size
                     # Update cache
                     self._font_cache[new_size] = self.time_font
+                    # Sprites were rasterized with the previous font size
+                    self._use_time_sprites()
                     logging.debug(f"Font size varied: {new_size}px (offset: {self._font_size_offset:+d}px)")
             except Exception as e:
                 logging.warning(f"Font variation failed: {e}")
@@ -1297,17 +2957,27 @@ class FramebufferClock:
         # One-time full clear on first render to remove balena background
         if not hasattr(self, '_initial_clear_done'):
             logging.info("Initial framebuffer clear to remove boot background")
-            self.fb_shadow.fill(0)
+            self._clear_shadow()
+            self._full_redraw = True
             self.write_to_framebuffer(None)
             self._initial_clear_done = True
         
         try:
             # If screensaver, write blank and return
             if not self.should_show_display():
-                logging.debug("Screensaver active - blanking display")
-                self.fb_shadow.fill(0)
-                self.write_to_framebuffer(None)
+                # Blank once on entry; the screen stays black until the window ends
+                if not self._screensaver_blanked:
+                    logging.debug("Screensaver active - blanking display")
+                    self.fb_shadow.fill(self._fb_alpha_mask)
+                    self._full_redraw = True
+                    self.write_to_framebuffer(None)
+                    self._screensaver_blanked = True
                 return
+            if self._screensaver_blanked and self._has_background():
+                # Repaint the background that the screensaver blanked
+                self._clear_shadow()
+                self._full_redraw = True
+            self._screensaver_blanked = False
         except Exception as e:
             logging.error(f"Error in render setup: {e}", exc_info=True)
             return
@@ -1318,7 +2988,11 @@ class FramebufferClock:
         # Get current time
         now = datetime.now()
         time_str = self.format_time(now)
+        # Colon is hidden on odd seconds; blinking pauses while the display is fully dimmed
+        self._colon_visible = not (self.blink_colon and self.current_brightness > 0 and now.second % 2)
+        time_on, date_on = self._blink_shown = self._blink_state(time.time())
         date_str = self.format_date(now)
+        self._shown_texts = (time_str if self.show_time else '', date_str if self.show_date else '')
         
         # Detect pixel shift change and clear old positions to prevent artifacts
         shift_changed = (self.pixel_shift_x != self._prev_pixel_shift_x or 
@@ -1326,8 +3000,9 @@ class FramebufferClock:
         if shift_changed:
             # Full framebuffer clear to eliminate all artifacts
             logging.info(f"Pixel shift: ({self._prev_pixel_shift_x},{self._prev_pixel_shift_y}) → ({self.pixel_shift_x},{self.pixel_shift_y}), clearing screen")
-            self.fb_shadow.fill(0)
+            self._clear_shadow()
             # Write the clear immediately before drawing new content
+            self._full_redraw = True
             self.write_to_framebuffer(None)
             # Reset all tracked rects
             self._last_time_rect = None
@@ -1335,12 +3010,18 @@ class FramebufferClock:
             self._last_status_rect = None
             if hasattr(self, '_last_weather_rect'):
                 self._last_weather_rect = None
+            for box in self.text_boxes + self._extra_lines:
+                setattr(self, box['rect_attr'], None)
             # Update tracking
             self._prev_pixel_shift_x = self.pixel_shift_x
             self._prev_pixel_shift_y = self.pixel_shift_y
         
         # Apply brightness
-        display_color = self.apply_brightness(self.color)
+        display_color = self.apply_brightness(self.apply_night_color(self.color))
+        date_display_color = self.apply_brightness(self.apply_night_color(self.date_color))
+        if self._countdown_phase(now) == 'alarm':
+            # A finished countdown flashes at full brightness, even at night
+            display_color, date_display_color = self.color, self.date_color
         status_color = self.apply_brightness(self.status_color)
         
         t_prep = time.time()
@@ -1353,28 +3034,32 @@ class FramebufferClock:
         
         # Dynamic margins and offsets (use cached scale)
         margin = int(30 * self.display_scale)
-        time_offset_y = int(60 * self.display_scale)
-        date_offset_y = int(100 * self.display_scale)
+        
+        # Shrink fonts that would be clipped by the margins
+        self._fit_width = self.fb_width - 2 * margin
+        analog = self.time_style == 'analog'
+        if self.size_auto_time and self.show_time and not analog:
+            self._apply_size_auto_time(time_str)
+        if self.auto_shrink_time and self.show_time and not analog:
+            self._fit_time_font(time_str, self._fit_width)
+        if self.auto_fit_date and self.show_date:
+            self._fit_date_font(date_str, self._fit_width)
         
         # Render time using pre-rendered sprite cache (7-15x faster)
         t_cache_start = time.time()
-        time_result = self._composite_time_from_cache(time_str, display_color)
+        time_result = self._composite_time_from_cache(time_str, display_color) if self.show_time and not analog else None
         cache_time_ms = (time.time() - t_cache_start) * 1000
         
-        if time_result:
-            # Result is (rgb565_array, width, height)
-            time_rgb565, time_w, time_h = time_result
-            time_x = max(margin, min(self.fb_width - margin - time_w, center_x_time - (time_w // 2)))
-            time_y = max(margin, min(self.fb_height - margin - time_h, center_y - time_offset_y - (time_h // 2)))
-            t_blit_start = time.time()
-            self.blit_rgb565_direct(time_rgb565, time_x, time_y, clear_last_rect_attr='_last_time_rect', skip_write=True, clear_full_region=True)
-            blit_time_ms = (time.time() - t_blit_start) * 1000
-            logging.info(f"Time: cache={cache_time_ms:.1f}ms, blit={blit_time_ms:.1f}ms")
-            if not hasattr(self, '_cache_hit_logged'):
-                logging.info(f"Sprite cache HIT: rendered time in {cache_time_ms:.1f}ms (vs 750ms direct)")
-                self._cache_hit_logged = True
-        else:
-            # Fallback to direct rendering if cache fails (shouldn't happen)
+        # Composite date up front so the whole block can be aligned
+        t_date_start = time.time()
+        date_result = self._composite_date_from_cache(date_str, date_display_color) if self.show_date else None
+        date_cache_ms = (time.time() - t_date_start) * 1000
+        
+        # Fallback to direct rendering if the caches miss (shouldn't happen)
+        time_img = date_img = None
+        if self.show_time and analog:
+            time_img = self._render_analog_face(now, display_color)
+        elif self.show_time and not time_result:
             logging.warning(f"Sprite cache MISS for time_str='{time_str}', falling back to direct rendering")
             if not self._temp_draw:
                 self._temp_draw = ImageDraw.Draw(Image.new('RGB', (1,1)))
@@ -1384,29 +3069,7 @@ class FramebufferClock:
             t_pad = max(40, int(self.time_font_size * 0.15))
             time_img = Image.new('RGB', (text_w + 2*t_pad, text_h + 2*t_pad), (0,0,0))
             ImageDraw.Draw(time_img).text((t_pad - time_bbox[0], t_pad - time_bbox[1]), time_str, font=self.time_font, fill=display_color)
-            time_x = max(margin, min(self.fb_width - margin - time_img.width, center_x_time - (time_img.width // 2)))
-            time_y = max(margin, min(self.fb_height - margin - time_img.height, center_y - time_offset_y - (time_img.height // 2)))
-            self.blit_rgb_image(time_img, time_x, time_y, clear_last_rect_attr='_last_time_rect', skip_write=True, clear_full_region=True)
-        
-        # Render date with generous padding - try sprite cache first
-        t_date_start = time.time()
-        date_result = self._composite_date_from_cache(date_str, display_color)
-        date_cache_ms = (time.time() - t_date_start) * 1000
-        
-        if date_result:
-            # Result is (rgb565_array, width, height)
-            date_rgb565, date_w, date_h = date_result
-            date_x = max(margin, min(self.fb_width - margin - date_w, center_x - (date_w // 2)))
-            date_y = max(margin, min(self.fb_height - margin - date_h, center_y + date_offset_y))
-            t_blit_start = time.time()
-            self.blit_rgb565_direct(date_rgb565, date_x, date_y, clear_last_rect_attr='_last_date_rect', skip_write=True, clear_full_region=True)
-            blit_date_ms = (time.time() - t_blit_start) * 1000
-            logging.info(f"Date: cache={date_cache_ms:.1f}ms, blit={blit_date_ms:.1f}ms")
-            if not hasattr(self, '_date_cache_hit_logged'):
-                logging.info(f"Date sprite cache HIT: rendered '{date_str}' in {date_cache_ms:.1f}ms")
-                self._date_cache_hit_logged = True
-        else:
-            # Fallback to direct rendering (slow path)
+        if self.show_date and not date_result:
             if not hasattr(self, '_date_cache_miss_logged'):
                 logging.warning(f"Date sprite cache MISS for '{date_str}', using slow direct rendering")
                 self._date_cache_miss_logged = True
@@ -1419,13 +3082,64 @@ class FramebufferClock:
             d_pad_right = max(40, int(self.date_font_size * 0.4))
             d_pad_top = max(20, int(self.date_font_size * 0.2))
             d_pad_bottom = max(20, int(self.date_font_size * 0.2))
-            date_canvas_w = date_w + d_pad_left + d_pad_right
-            date_canvas_h = date_h + d_pad_top + d_pad_bottom
-            date_x = max(margin, min(self.fb_width - margin - date_canvas_w, center_x - date_canvas_w // 2))
-            date_y = max(margin, min(self.fb_height - margin - date_canvas_h, center_y + date_offset_y))
-            date_img = Image.new('RGB', (date_canvas_w, date_canvas_h), (0,0,0))
-            ImageDraw.Draw(date_img).text((d_pad_left - date_bbox[0], d_pad_top - date_bbox[1]), date_str, font=self.date_font, fill=display_color)
-            self.blit_rgb_image(date_img, date_x, date_y, clear_last_rect_attr='_last_date_rect', skip_write=True, clear_full_region=True)
+            date_img = Image.new('RGB', (date_w + d_pad_left + d_pad_right, date_h + d_pad_top + d_pad_bottom), (0,0,0))
+            ImageDraw.Draw(date_img).text((d_pad_left - date_bbox[0], d_pad_top - date_bbox[1]), date_str, font=self.date_font, fill=date_display_color)
+        
+        # Place the time/date block; a hidden line takes no space, so a lone time or date centers on its own
+        time_size = (time_result[1], time_result[2]) if time_result else time_img.size if time_img else (0, 0)
+        date_size = (date_result[1], date_result[2]) if date_result else date_img.size if date_img else (0, 0)
+        center_y, (time_x, time_y), (date_x, date_y) = self._place_time_date(
+            time_size, date_size, center_x_time, center_x, center_y, margin)
+        
+        if not self.show_time or not time_on:
+            self._clear_last_rect('_last_time_rect')
+        elif time_result:
+            # Result is (rgb565_array, width, height, alpha)
+            time_rgb565, _, _, time_alpha = time_result
+            t_blit_start = time.time()
+            self.blit_rgb565_direct(time_rgb565, time_x, time_y, clear_last_rect_attr='_last_time_rect', skip_write=True, clear_full_region=True, text_color=display_color, alpha=time_alpha)
+            blit_time_ms = (time.time() - t_blit_start) * 1000
+            logging.info(f"Time: cache={cache_time_ms:.1f}ms, blit={blit_time_ms:.1f}ms")
+            if not hasattr(self, '_cache_hit_logged'):
+                logging.info(f"Sprite cache HIT: rendered time in {cache_time_ms:.1f}ms (vs 750ms direct)")
+                self._cache_hit_logged = True
+        else:
+            self.blit_rgb_image(time_img, time_x, time_y, clear_last_rect_attr='_last_time_rect', skip_write=True, clear_full_region=True, text_color=display_color)
+        
+        # Render date with generous padding
+        if not self.show_date or not date_on:
+            self._clear_last_rect('_last_date_rect')
+        elif date_result:
+            # Result is (rgb565_array, width, height, alpha)
+            date_rgb565, _, _, date_alpha = date_result
+            t_blit_start = time.time()
+            self.blit_rgb565_direct(date_rgb565, date_x, date_y, clear_last_rect_attr='_last_date_rect', skip_write=True, clear_full_region=True, text_color=date_display_color, alpha=date_alpha)
+            blit_date_ms = (time.time() - t_blit_start) * 1000
+            logging.info(f"Date: cache={date_cache_ms:.1f}ms, blit={blit_date_ms:.1f}ms")
+            if not hasattr(self, '_date_cache_hit_logged'):
+                logging.info(f"Date sprite cache HIT: rendered '{date_str}' in {date_cache_ms:.1f}ms")
+                self._date_cache_hit_logged = True
+        else:
+            self.blit_rgb_image(date_img, date_x, date_y, clear_last_rect_attr='_last_date_rect', skip_write=True, clear_full_region=True, text_color=date_display_color)
+        
+        # Borders go over the freshly blitted time/date canvases (BORDER_TIME/BORDER_DATE)
+        for border, rect_attr in ((self.border_time, '_last_time_rect'), (self.border_date, '_last_date_rect')):
+            rect = getattr(self, rect_attr)
+            if border and rect:
+                self._draw_rect_outline(rect, border[0], self.apply_brightness(border[1]))
+        
+        # Extra lines stack under the date (or the time), aligned like the date, EXTRA_LINES_GAP apart
+        if self.extra_lines_file:
+            self._read_extra_lines()
+            gap = int(self.extra_lines_gap * self.display_scale)
+            line_y = (date_y + date_size[1] if self.show_date else time_y + time_size[1]) + gap
+            for box in self._extra_lines:
+                box_color = self.apply_brightness(self.apply_night_color(box['color']))
+                box_img = self._render_text_box(box, box_color)
+                box_x = max(0, min(self.fb_width - box_img.width, self._aligned_x(box_img.width, center_x, margin, self.date_align_h)))
+                self.blit_rgb_image(box_img, box_x, min(line_y, self.fb_height - box_img.height), clear_last_rect_attr=box['rect_attr'],
+                                    skip_write=True, clear_full_region=True, text_color=box_color)
+                line_y += box_img.height + gap
         
         # Draw weather if available (measure, pad, and blit like time/date)
         if self.weather_text:
@@ -1440,9 +3154,19 @@ class FramebufferClock:
             w_pad_bottom = max(6, int(self.weather_font_size * 0.12))
             weather_img = Image.new('RGB', (ww + w_pad_left + w_pad_right, wh + w_pad_top + w_pad_bottom), (0,0,0))
             ImageDraw.Draw(weather_img).text((w_pad_left - wb[0], w_pad_top - wb[1]), self.weather_text, font=self.weather_font, fill=display_color)
-            weather_x = center_x - (weather_img.width // 2)
+            weather_x = self._aligned_x(weather_img.width, center_x, margin)
             weather_y = center_y + int(120 * self.display_scale)
-            self.blit_rgb_image(weather_img, weather_x, weather_y, clear_last_rect_attr='_last_weather_rect', skip_write=True)
+            self.blit_rgb_image(weather_img, weather_x, weather_y, clear_last_rect_attr='_last_weather_rect', skip_write=True, text_color=display_color)
+        
+        # Text boxes: centered on their fractional position, moved by the pixel shift, kept on screen
+        for box in self.text_boxes:
+            box_color = self.apply_brightness(self.apply_night_color(box['color']))
+            box_img = self._render_text_box(box, box_color)
+            box_x = max(0, min(self.fb_width - box_img.width,
+                               int(box['x'] * self.fb_width) - box_img.width // 2 + self.pixel_shift_x))
+            box_y = max(0, min(self.fb_height - box_img.height,
+                               int(box['y'] * self.fb_height) - box_img.height // 2 + self.pixel_shift_y))
+            self.blit_rgb_image(box_img, box_x, box_y, clear_last_rect_attr=box['rect_attr'], skip_write=True, text_color=box_color)
         
         # Draw status bar
         if self.show_status_bar:
@@ -1503,6 +3227,7 @@ class FramebufferClock:
             self.blit_rgb_image(cur_img, px, py, clear_last_rect_attr='_last_cursor_rect', skip_write=True)
         # Write shadow buffer to framebuffer ONCE at the end
         self.write_to_framebuffer(None)
+        self.frames_rendered += 1
         t_write = time.time()
         
         # Log timing breakdown
@@ -1514,12 +3239,16 @@ class FramebufferClock:
     
     def write_to_framebuffer(self, image):
         """Write image directly to framebuffer device.
-        If using 16bpp shadow buffer, write only dirty rectangles.
+        With a shadow buffer, write only dirty rectangles; the whole shadow is copied
+        only when a full redraw was requested (first frame, screen clears).
         """
         try:
+            if self._png_dir and self.fb_format:
+                self._write_png_frame()
             # For partial-update path, write only dirty rects if present
-            if self.fb_bpp == 16 and isinstance(self.fb_shadow, np.ndarray):
-                if getattr(self, '_dirty_rects', None):
+            elif self.fb_format and isinstance(self.fb_shadow, np.ndarray):
+                bpp_bytes = self._fb_bytes_per_pixel
+                if not self._full_redraw and self._dirty_rects:
                     if self.fb_mmap:
                         # Use memory map for fast row copies
                         for (rx, ry, rw, rh) in self._dirty_rects:
@@ -1531,15 +3260,17 @@ class FramebufferClock:
                             rh = max(0, min(self.fb_height - ry, rh))
                             if rw == 0 or rh == 0:
                                 continue
-                            for row in range(rh):
-                                offset = ((ry + row) * self._fb_stride_bytes) + (rx * 2)
-                                slice_row = self.fb_shadow[ry + row, rx:rx+rw]
-                                self.fb_mmap[offset:offset + (rw * 2)] = slice_row.astype('<u2').tobytes()
+                            block, px, py = self._to_physical(self.fb_shadow[ry:ry+rh, rx:rx+rw], rx, ry)
+                            block_bytes = block.shape[1] * bpp_bytes
+                            for row in range(block.shape[0]):
+                                offset = ((py + row) * self._fb_stride_bytes) + (px * bpp_bytes)
+                                self.fb_mmap[offset:offset + block_bytes] = block[row].tobytes()
+                            self.fb_bytes_written += rw * rh * bpp_bytes
                         self._dirty_rects.clear()
                     else:
                         # Fallback to file writes with seek
                         with open(self.fb_device, 'r+b') as fb:
-                            stride_bytes = self.fb_width * 2
+                            stride_bytes = self._fb_stride_bytes
                             for (rx, ry, rw, rh) in self._dirty_rects:
                                 if rw <= 0 or rh <= 0:
                                     continue
@@ -1549,32 +3280,46 @@ class FramebufferClock:
                                 rh = max(0, min(self.fb_height - ry, rh))
                                 if rw == 0 or rh == 0:
                                     continue
-                                for row in range(rh):
-                                    offset = ((ry + row) * stride_bytes) + (rx * 2)
+                                block, px, py = self._to_physical(self.fb_shadow[ry:ry+rh, rx:rx+rw], rx, ry)
+                                for row in range(block.shape[0]):
+                                    offset = ((py + row) * stride_bytes) + (px * bpp_bytes)
                                     fb.seek(offset)
-                                    slice_row = self.fb_shadow[ry + row, rx:rx+rw]
-                                    fb.write(slice_row.astype('<u2').tobytes())
+                                    fb.write(block[row].tobytes())
+                                self.fb_bytes_written += rw * rh * bpp_bytes
                             self._dirty_rects.clear()
-                else:
-                    # No dirty rects tracked; fallback to full shadow write
+                elif self._full_redraw:
+                    # Full redraw requested: copy the whole shadow buffer
+                    shadow, _, _ = self._to_physical(self.fb_shadow, 0, 0)
                     if self.fb_mmap:
                         # Copy entire shadow into mmap in chunks to avoid huge temporary buffers
-                        for row in range(self.fb_height):
+                        row_bytes = shadow.shape[1] * bpp_bytes
+                        for row in range(shadow.shape[0]):
                             offset = (row * self._fb_stride_bytes)
-                            slice_row = self.fb_shadow[row, :]
-                            self.fb_mmap[offset:offset + self._fb_stride_bytes] = slice_row.astype('<u2').tobytes()
-                    else:
+                            slice_row = shadow[row, :]
+                            self.fb_mmap[offset:offset + row_bytes] = slice_row.tobytes()
+                    elif self._fb_stride_bytes == shadow.shape[1] * bpp_bytes:
                         with open(self.fb_device, 'wb') as fb:
-                            fb.write(self.fb_shadow.astype('<u2').tobytes())
+                            fb.write(shadow.tobytes())
+                    else:
+                        # Padded rows: seek to each scanline start
+                        with open(self.fb_device, 'r+b') as fb:
+                            for row in range(shadow.shape[0]):
+                                fb.seek(row * self._fb_stride_bytes)
+                                fb.write(shadow[row, :].tobytes())
+                    self.fb_bytes_written += self.fb_width * self.fb_height * bpp_bytes
+                    self._dirty_rects.clear()
+                    self._full_redraw = False
             else:
                 # Fallback: full-frame conversion from provided image
+                if self.rotation:
+                    image = image.rotate(-self.rotation, expand=True)
                 if self.fb_bpp == 32:
                     buf = image.convert('BGRA').tobytes()
                 elif self.fb_bpp == 24:
                     buf = image.convert('BGR').tobytes()
                 elif self.fb_bpp == 16:
                     rgb_image = image.convert('RGB')
-                    arr = np.frombuffer(rgb_image.tobytes(), dtype=np.uint8).reshape((self.fb_height, self.fb_width, 3))
+                    arr = np.frombuffer(rgb_image.tobytes(), dtype=np.uint8).reshape((rgb_image.height, rgb_image.width, 3))
                     r = (arr[:, :, 0] >> 3).astype(np.uint16)
                     g = (arr[:, :, 1] >> 2).astype(np.uint16)
                     b = (arr[:, :, 2] >> 3).astype(np.uint16)
@@ -1583,16 +3328,24 @@ class FramebufferClock:
                     buf = image.convert('BGR').tobytes()
                 with open(self.fb_device, 'wb') as fb:
                     fb.write(buf)
+                self.fb_bytes_written += len(buf)
         except Exception as e:
             logging.error(f"Failed to write to framebuffer: {e}")
+            if isinstance(e, OSError) and not self._png_dir:
+                # Some rects may be half-written and retries would only pile up more: repaint everything instead
+                self._dirty_rects.clear()
+                self._full_redraw = True
+                self.reopen_framebuffer()
 
 
-    def blit_rgb565_direct(self, rgb565_array: np.ndarray, x: int, y: int, clear_last_rect_attr: str, skip_write: bool = False, clear_full_region: bool = False):
+    def blit_rgb565_direct(self, rgb565_array: np.ndarray, x: int, y: int, clear_last_rect_attr: str, skip_write: bool = False, clear_full_region: bool = False, text_color: Optional[tuple] = None, alpha: Optional[np.ndarray] = None):
         """Blit pre-converted RGB565 array directly to framebuffer (ultra-fast, no conversion).
         rgb565_array: 2D numpy array of uint16 RGB565 pixels
         Clears previous rect if position/size changed.
+        text_color: color the text was composited in; blends it onto a non-black background.
+        alpha: per-pixel uint8 coverage for multi-colored (styled) text, used instead of text_color.
         """
-        if self.fb_bpp != 16 or not isinstance(self.fb_shadow, np.ndarray):
+        if not self.fb_format or not isinstance(self.fb_shadow, np.ndarray):
             return  # Can't use fast path
         
         h, w = rgb565_array.shape
@@ -1634,10 +3387,16 @@ class FramebufferClock:
                 clear_x2 = min(self.fb_width, lx + lw + clear_pad)
                 clear_y2 = min(self.fb_height, ly + lh + clear_pad)
             
-            self.fb_shadow[clear_y1:clear_y2, clear_x1:clear_x2].fill(0)
-        
-        # Blit RGB565 directly (NO conversion needed!)
-        self.fb_shadow[y:y2, x:x2] = rgb565_array[:h_clamp, :w_clamp]
+            self._clear_shadow(clear_x1, clear_y1, clear_x2, clear_y2)
+        
+        # Blit RGB565 directly (NO conversion needed on 16bpp with a black background!)
+        pixels = rgb565_array[:h_clamp, :w_clamp]
+        if (text_color is not None or alpha is not None) and self._has_background():
+            coverage = alpha[:h_clamp, :w_clamp] if alpha is not None else None
+            self.fb_shadow[y:y2, x:x2] = self._pack_rgb(
+                self._blend_over_background(self._rgb565_to_rgb888(pixels), text_color, x, y, coverage))
+        else:
+            self.fb_shadow[y:y2, x:x2] = self._rgb565_to_native(pixels)
         
         # Store rect
         rect = (x, y, w_clamp, h_clamp)
@@ -1646,13 +3405,24 @@ class FramebufferClock:
         if hasattr(self, '_dirty_rects'):
             self._dirty_rects.append(rect)
     
-    def blit_rgb_image(self, img: Image.Image, x: int, y: int, clear_last_rect_attr: str, skip_write: bool = False, clear_full_region: bool = False):
+    def _clear_last_rect(self, rect_attr: str):
+        """Erase an element that is no longer drawn (SHOW_TIME/SHOW_DATE off) and forget its rect."""
+        rect = getattr(self, rect_attr, None)
+        if rect:
+            x, y, w, h = rect
+            self._clear_shadow(x, y, x + w, y + h)
+            self._dirty_rects.append(rect)
+            setattr(self, rect_attr, None)
+    
+    def blit_rgb_image(self, img: Image.Image, x: int, y: int, clear_last_rect_attr: str, skip_write: bool = False, clear_full_region: bool = False, text_color: Optional[tuple] = None):
         """Convert a small RGB888 PIL image to RGB565 and blit into shadow buffer at (x,y).
         Clears previous rect stored in the attribute to avoid trails.
         If clear_full_region=True, clears union of previous and current rect (fixes artifacts from width changes).
         If skip_write=True, don't write to framebuffer yet (batch writes).
+        text_color: color the image was drawn in; black/antialiased pixels are blended onto the
+        background by coverage against it. None copies the image as-is (opaque panels like the menu).
         """
-        if self.fb_bpp != 16 or not isinstance(self.fb_shadow, np.ndarray):
+        if not self.fb_format or not isinstance(self.fb_shadow, np.ndarray):
             # Fallback: draw onto a full-size image (rare path)
             full = Image.new('RGB', (self.fb_width, self.fb_height), self.bg_color)
             full.paste(img, (x, y))
@@ -1703,16 +3473,14 @@ class FramebufferClock:
                 clear_x2 = min(self.fb_width, lx + lw + clear_pad)
                 clear_y2 = min(self.fb_height, ly + lh + clear_pad)
             
-            self.fb_shadow[clear_y1:clear_y2, clear_x1:clear_x2].fill(0)
+            self._clear_shadow(clear_x1, clear_y1, clear_x2, clear_y2)
         
-        # Convert to RGB565
+        # Convert to native framebuffer format
         arr = np.frombuffer(img.tobytes(), dtype=np.uint8).reshape((img.height, img.width, 3))[:h_clamp, :w_clamp]
-        r = (arr[:, :, 0] >> 3).astype(np.uint16)
-        g = (arr[:, :, 1] >> 2).astype(np.uint16)
-        b = (arr[:, :, 2] >> 3).astype(np.uint16)
-        rgb565 = ((r << 11) | (g << 5) | b)
+        if text_color is not None:
+            arr = self._blend_over_background(arr, text_color, x, y)
         # Blit into shadow
-        self.fb_shadow[y:y2, x:x2] = rgb565
+        self.fb_shadow[y:y2, x:x2] = self._pack_rgb(arr)
         # Store rect
         rect = (x, y, w_clamp, h_clamp)
         setattr(self, clear_last_rect_attr, rect)
@@ -1806,7 +3574,8 @@ class FramebufferClock:
             # Clear menu rect
             if hasattr(self, '_last_menu_rect'):
                 lx, ly, lw, lh = self._last_menu_rect
-                self.fb_shadow[ly:ly+lh, lx:lx+lw].fill(0)
+                self._clear_shadow(lx, ly, lx + lw, ly + lh)
+                self._dirty_rects.append(self._last_menu_rect)
                 self._last_menu_rect = None
             logging.info("Settings menu closed")
             return
@@ -1836,6 +3605,20 @@ class FramebufferClock:
         logging.info("Starting framebuffer clock display loop")
         logging.info("Press 'S' key to open settings menu")
         
+        # Signal handlers only set flags and write to this pipe, which wakes the loop's sleep
+        self._wake_fds = os.pipe()
+        for fd in self._wake_fds:
+            os.set_blocking(fd, False)
+        # Stop cleanly (and blank the screen) when the container/service is stopped
+        signal.signal(signal.SIGTERM, self._handle_signal)
+        signal.signal(signal.SIGINT, self._handle_signal)
+        # `systemctl reload` / `kill -HUP`: rebuild from the re-read config files without a restart
+        signal.signal(signal.SIGHUP, self._handle_reload)
+        # `kill -USR1`: dump the current state to STATUS_FILE (e.g. to confirm a config change applied)
+        signal.signal(signal.SIGUSR1, self._handle_status)
+        # `kill -USR2`: save the current frame to SNAPSHOT_FILE
+        signal.signal(signal.SIGUSR2, self._handle_status)
+        
         # Initial updates
         self.update_weather()
         self.check_network_status()
@@ -1855,14 +3638,22 @@ class FramebufferClock:
                 current_second = datetime.now().second
                 current_minute = datetime.now().minute
                 
-                # Decide whether to render this loop
-                if self.show_seconds:
+                # Decide whether to render this loop (a countdown ticks every second too)
+                per_second = self.show_seconds or self.blink_colon or self._countdown_phase(datetime.now()) is not None
+                if per_second:
                     render_due = (current_second != last_second) or (current_minute != last_minute)
                 else:
                     # Throttle: when seconds are hidden, redraw on minute change (reduces CPU)
                     render_due = (current_minute != last_minute)
+                # A blinking time/date line also redraws whenever it toggles
+                if self._blink_state(time.time()) != self._blink_shown:
+                    render_due = True
 
                 if render_due:
+                    if current_minute != last_minute:
+                        # Re-read /etc/localtime so timezone changes apply without a restart
+                        time.tzset()
+                    
                     # Skip expensive updates if overlay is showing
                     if not self.show_settings_overlay:
                         # Update weather periodically
@@ -1886,7 +3677,17 @@ class FramebufferClock:
                     
                     if frame_count % 300 == 0:  # Log every 5 minutes (reduce I/O)
                         avg_loops = loop_count / frame_count if frame_count > 0 else 0
-                        logging.info(f"Clock stats: {frame_count} renders, {loop_count} loops, {avg_loops:.1f} loops/render")
+                        avg_kb = self.fb_bytes_written / frame_count / 1024
+                        logging.info(f"Clock stats: {frame_count} renders, {loop_count} loops, {avg_loops:.1f} loops/render, "
+                                     f"{avg_kb:.1f} KB/render written to framebuffer, "
+                                     f"sprite cache {self.sprite_cache_hits} hits/{self.sprite_cache_misses} misses")
+                
+                if self.status_requested:
+                    self.status_requested = False
+                    self.write_status()
+                if self.snapshot_requested:
+                    self.snapshot_requested = False
+                    self.write_snapshot(self.snapshot_file)
                 
                 # Check for restart flag
                 if os.path.exists('/tmp/restart_clock'):
@@ -1901,42 +3702,352 @@ class FramebufferClock:
                 if self.show_settings_overlay:
                     hz = getattr(self, 'overlay_refresh_hz', 10.0)
                     interval = 1.0 / max(1.0, float(hz))
-                    time.sleep(interval)
-                elif not self.show_seconds and not self.show_settings_overlay:
+                    self._sleep(interval)
+                elif not per_second and not self.show_settings_overlay:
                     now_ts = time.time()
                     next_minute = (math.floor(now_ts / 60.0) * 60.0) + 60.0
                     delay = max(0.01, next_minute - now_ts)  # Minimum 10ms
-                    time.sleep(delay)
+                    self._sleep(self._until_blink_toggle(delay))
                 else:
-                    # With seconds shown: align to next second boundary
+                    # With seconds shown (or colon blinking): align to next second boundary
                     now_ts = time.time()
                     next_second = math.floor(now_ts) + 1.0
                     delay = max(0.01, next_second - now_ts)  # Minimum 10ms to prevent tight loop
-                    time.sleep(delay)
+                    self._sleep(self._until_blink_toggle(delay))
         
         except KeyboardInterrupt:
             logging.info("Clock interrupted by user")
         except Exception as e:
             logging.error(f"Error in clock loop: {e}", exc_info=True)
         finally:
-            self.cleanup()
+            # A SIGHUP from here on is ignored rather than turning this exit into a reload
+            self.running = False
+            if not self.reload_requested:
+                # On reload main() validates the new config first and keeps this frame up meanwhile
+                self.cleanup()
+            for fd in self._wake_fds or ():
+                os.close(fd)
+            self._wake_fds = None
+    
+    def _sleep(self, seconds: float):
+        """Sleep up to `seconds`, returning early once a signal handler pokes the wake pipe."""
+        readable, _, _ = select.select([self._wake_fds[0]], [], [], seconds)
+        if readable:
+            try:
+                os.read(self._wake_fds[0], 64)
+            except OSError:
+                pass
     
-    def cleanup(self):
-        """Cleanup resources."""
+    def _handle_reload(self, signum, frame):
+        """Flag a reload on SIGHUP and wake the main loop; main() then recreates the clock.
+        Only sets state, so a signal landing mid-render or mid-cleanup can't abort it.
+        """
+        if not self.running or self._wake_fds is None:
+            return
+        logging.info("Received SIGHUP, reloading configuration")
+        self.reload_requested = True
+        self.running = False
+        try:
+            os.write(self._wake_fds[1], b'\0')
+        except OSError:
+            pass
+    
+    def _handle_status(self, signum, frame):
+        """Flag a status dump (SIGUSR1) or snapshot (SIGUSR2) and wake the main loop, which writes it between frames."""
+        if signum == signal.SIGUSR2:
+            self.snapshot_requested = True
+        else:
+            self.status_requested = True
+        if self._wake_fds is not None:
+            try:
+                os.write(self._wake_fds[1], b'\0')
+            except OSError:
+                pass
+    
+    def status(self) -> dict:
+        """Snapshot of what the clock is currently showing and how (for write_status)."""
+        time_text, date_text = self._shown_texts
+        return {
+            'fb_width': self.fb_width,
+            'fb_height': self.fb_height,
+            'rotation': self.rotation,
+            'pixel_format': self.fb_format,
+            'time_text': time_text,
+            'date_text': date_text,
+            'color': '#%02X%02X%02X' % self.color,
+            'date_color': '#%02X%02X%02X' % self.date_color,
+            'brightness': round(self.current_brightness, 3),
+            'shift': [self.pixel_shift_x, self.pixel_shift_y],
+            'time_font_size': self.time_font_size,
+            'date_font_size': self.date_font_size,
+            'time_font': self.time_font_file,
+            'date_font': self.date_font_file,
+            'frames': self.frames_rendered,
+        }
+    
+    def write_status(self):
+        """Write status() as one newline-terminated JSON line to STATUS_FILE, replacing it atomically
+        so a reader never sees a partial file.
+        """
+        tmp_path = f"{self.status_file}.tmp"
+        try:
+            with open(tmp_path, 'w') as f:
+                f.write(json.dumps(self.status()) + '\n')
+                f.flush()
+            os.replace(tmp_path, self.status_file)
+            logging.info(f"Status written to {self.status_file}")
+        except OSError as e:
+            logging.warning(f"Could not write status to {self.status_file}: {e}")
+    
+    def _handle_signal(self, signum, frame):
+        """Stop the main loop on SIGTERM/SIGINT; run() then calls cleanup(), which blanks the framebuffer.
+        Only sets state and pokes the wake pipe (cutting a minute-long sleep short), so a signal
+        landing mid-write can't leave a half-drawn frame. Shutting down wins over a pending reload.
+        """
+        logging.info(f"Received {signal.Signals(signum).name}, shutting down")
+        self.running = False
+        self.reload_requested = False
+        if self._wake_fds is not None:
+            try:
+                os.write(self._wake_fds[1], b'\0')
+            except OSError:
+                pass
+    
+    def cleanup(self, blank: bool = True):
+        """Cleanup resources.
+        blank=False leaves the last frame on screen (a reload hands the display straight to a new clock).
+        """
         logging.info("Framebuffer clock stopped")
+        # Blank the screen so the last frame doesn't stay on the display after exit
         try:
+            if blank:
+                self.fb_shadow.fill(self._fb_alpha_mask)
+                self._full_redraw = True
+                self.write_to_framebuffer(Image.new('RGB', (self.fb_width, self.fb_height), (0, 0, 0)))
             if getattr(self, 'fb_mmap', None):
-                self.fb_mmap.close()
-            if getattr(self, '_fb_file', None):
-                self._fb_file.close()
-        except Exception:
-            pass
+                self.fb_mmap.flush()
+        except Exception as e:
+            logging.warning(f"Failed to clear framebuffer on exit: {e}")
+        if self._backlight_initial is not None and self.brightness_mode != 'sw':
+            # Don't leave the panel at night level (or off) once the clock is gone
+            self.set_backlight(self._backlight_initial)
+        if self.light_sensor:
+            # Stop sampling so a SIGHUP reload can reopen the bus without leaking the old thread/fd
+            self.light_sensor.close()
+            self.light_sensor = None
+        self._unmap_framebuffer()
+
+
+# Fonts tried in order when FONT_PATH is unset or unreadable (Helvetica-like faces)
+DEFAULT_FONT_PATHS = (
+    '/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf',
+    '/usr/share/fonts/truetype/liberation/LiberationSans-Bold.ttf',
+    '/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf',
+)
+
+
+def find_font_file() -> Optional[str]:
+    """The main font file: FONT_PATH if it loads, else the first of DEFAULT_FONT_PATHS that exists."""
+    custom_font = os.environ.get('FONT_PATH', '').strip()
+    if custom_font:
+        try:
+            ImageFont.truetype(custom_font, 12)
+            return custom_font
+        except Exception as e:
+            logging.warning(f"FONT_PATH={custom_font} could not be loaded ({e}), searching system fonts")
+    for path in DEFAULT_FONT_PATHS:
+        if os.path.exists(path):
+            return path
+    return None
+
+
+# Directories scanned by --list-fonts
+FONT_DIRS = ('/usr/share/fonts', '~/.local/share/fonts', '/usr/local/share/fonts')
+
+
+def list_font_files() -> list:
+    """Return sorted paths of all .ttf/.otf files under FONT_DIRS (for FONT_PATH etc.)."""
+    fonts = set()
+    for font_dir in FONT_DIRS:
+        for root, _dirs, files in os.walk(os.path.expanduser(font_dir)):
+            for name in files:
+                if name.lower().endswith(('.ttf', '.otf')):
+                    fonts.add(os.path.join(root, name))
+    return sorted(fonts)
+
+
+def print_glyph_metrics(font_path: str, size: int, text: str):
+    """Print Pillow's layout of text at size as a tab-separated table, one row per glyph:
+    pen position, ink bbox size and offsets from the pen position, and advance width.
+    """
+    font = ImageFont.truetype(font_path, size)
+    print(f"# {font_path} at {size}px")
+    print("char\tx\ty\twidth\theight\txmin\tymin\tadvance")
+    for i, char in enumerate(text):
+        x = font.getlength(text[:i])
+        left, top, right, bottom = font.getbbox(char)
+        print(f"{char!r}\t{x:.1f}\t0\t{right - left}\t{bottom - top}\t{left}\t{top}\t{font.getlength(char):.1f}")
+
+
+def positive_int(value: str) -> int:
+    """argparse type for counts that must be at least 1."""
+    try:
+        number = int(value)
+    except ValueError:
+        raise argparse.ArgumentTypeError(f"invalid integer: '{value}'")
+    if number < 1:
+        raise argparse.ArgumentTypeError(f"must be at least 1, got {number}")
+    return number
+
+
+def parse_args(argv=None):
+    """Parse command-line arguments."""
+    parser = argparse.ArgumentParser(description="Raspberry Pi framebuffer digital clock")
+    parser.add_argument('--config', type=Path, default=Path(__file__).parent / "config.yaml",
+                        help="Path to base YAML configuration (default: config.yaml next to this script)")
+    parser.add_argument('--list-fonts', action='store_true',
+                        help="Print available .ttf/.otf font paths (for FONT_PATH) and exit")
+    parser.add_argument('--benchmark', type=positive_int, metavar='FRAMES',
+                        help="Render FRAMES identical frames as fast as possible, print timings and exit")
+    parser.add_argument('--metrics', nargs=2, metavar=('SIZE', 'TEXT'),
+                        help="Print per-glyph layout metrics of TEXT in the time font (TIME_FONT, else FONT_PATH) "
+                             "at SIZE px and exit, to diagnose clipping or off-center text")
+    return parser.parse_args(argv)
+
+
+def run_benchmark(clock: FramebufferClock, frames: int):
+    """Render the current configuration frames times back to back and print FPS and a phase breakdown."""
+    phases = {'sprite composite': 0.0, 'framebuffer write': 0.0}
+    
+    def timed(phase, fn):
+        def wrapper(*args, **kwargs):
+            t = time.perf_counter()
+            try:
+                return fn(*args, **kwargs)
+            finally:
+                phases[phase] += time.perf_counter() - t
+        return wrapper
+    
+    clock._composite_time_from_cache = timed('sprite composite', clock._composite_time_from_cache)
+    clock._composite_date_from_cache = timed('sprite composite', clock._composite_date_from_cache)
+    clock.write_to_framebuffer = timed('framebuffer write', clock.write_to_framebuffer)
+    
+    root_logger = logging.getLogger()
+    log_level = root_logger.level
+    screensaver_enabled = clock.screensaver_enabled
+    clock.screensaver_enabled = False  # a blanked display would time an empty frame
+    try:
+        # Warm-up frame: initial clear and lazily rendered date sprites shouldn't count
+        clock.render()
+        for phase in phases:
+            phases[phase] = 0.0
+        root_logger.setLevel(logging.WARNING)  # per-frame timing logs would dominate the measurement
+        t_start = time.perf_counter()
+        for _ in range(frames):
+            clock.render()
+        elapsed = time.perf_counter() - t_start
+    finally:
+        root_logger.setLevel(log_level)
+        clock.screensaver_enabled = screensaver_enabled
+    
+    phases['layout, blending and blit'] = max(0.0, elapsed - sum(phases.values()))
+    print(f"{frames} frames in {elapsed:.3f}s: {frames / elapsed:.1f} FPS, {elapsed / frames * 1000:.2f} ms/frame")
+    for phase, total in phases.items():
+        print(f"  {phase:<28} {total / frames * 1000:8.2f} ms/frame ({total / elapsed * 100:5.1f}%)")
+
+
+def load_config(config_path, settings_path: Path) -> dict:
+    """Load config.yaml and apply the settings UI overrides from settings_path.
+    Raises on unreadable or malformed files.
+    """
+    # Load base configuration
+    with open(config_path, 'r') as f:
+        config = yaml.safe_load(f)
+    if not isinstance(config, dict):
+        raise ValueError(f"expected a YAML mapping, got {type(config).__name__}")
+    logging.info(f"Base configuration loaded from {config_path}")
+    
+    # Override with settings from UI if available
+    if settings_path.exists():
+        with open(settings_path, 'r') as f:
+            ui_settings = yaml.safe_load(f) or {}
+        logging.info(f"Loaded {len(ui_settings)} settings from settings UI")
+        
+        # Map UI setting names to config structure
+        # UI uses flat keys like WEATHER_ENABLED, config uses nested structure
+        if 'WEATHER_ENABLED' in ui_settings:
+            config['weather']['enabled'] = ui_settings['WEATHER_ENABLED']
+        if 'WEATHER_LOCATION' in ui_settings:
+            config['weather']['location'] = ui_settings['WEATHER_LOCATION']
+        if 'WEATHER_API_KEY' in ui_settings:
+            config['weather']['api_key'] = ui_settings['WEATHER_API_KEY']
+        if 'TIMEZONE' in ui_settings:
+            config.setdefault('time', {})
+            config['time']['timezone'] = ui_settings['TIMEZONE']
+        if 'DISPLAY_COLOR' in ui_settings:
+            config['display']['color'] = ui_settings['DISPLAY_COLOR']
+        if 'TIME_FORMAT' in ui_settings:
+            # '12' or '24' from UI -> boolean in config
+            config.setdefault('time', {})
+            config['time']['format_12h'] = (str(ui_settings['TIME_FORMAT']) == '12')
+        if 'DISPLAY_DATE' in ui_settings:
+            config.setdefault('display', {})
+            config['display']['show_date'] = bool(ui_settings['DISPLAY_DATE'])
+        if 'SHIFT_ENABLED' in ui_settings:
+            config.setdefault('display', {})
+            config['display']['pixel_shift_enabled'] = bool(ui_settings['SHIFT_ENABLED'])
+        if 'SHIFT_INTERVAL' in ui_settings:
+            config.setdefault('display', {})
+            try:
+                config['display']['pixel_shift_interval_seconds'] = int(ui_settings['SHIFT_INTERVAL'])
+            except Exception:
+                pass
+        if 'SHIFT_RANGE' in ui_settings:
+            config.setdefault('display', {})
+            try:
+                config['display']['pixel_shift_range'] = int(ui_settings['SHIFT_RANGE'])
+            except Exception:
+                pass
+        if 'SCREENSAVER_ENABLED' in ui_settings:
+            config.setdefault('display', {})
+            config['display']['screensaver_enabled'] = bool(ui_settings['SCREENSAVER_ENABLED'])
+        if 'SCREENSAVER_START' in ui_settings:
+            try:
+                start_hour = int(str(ui_settings['SCREENSAVER_START']).split(':')[0])
+                config.setdefault('display', {})
+                config['display']['screensaver_start_hour'] = start_hour
+            except Exception:
+                pass
+        if 'SCREENSAVER_END' in ui_settings:
+            try:
+                end_hour = int(str(ui_settings['SCREENSAVER_END']).split(':')[0])
+                config.setdefault('display', {})
+                config['display']['screensaver_end_hour'] = end_hour
+            except Exception:
+                pass
+    return config
 
 
 def main():
     """Main entry point."""
     from utils import setup_logging, load_build_info, log_runtime_summary
     
+    args = parse_args()
+    if args.list_fonts:
+        for path in list_font_files():
+            print(path)
+        return
+    if args.metrics:
+        size, text = args.metrics
+        font_path = os.environ.get('TIME_FONT', '').strip() or find_font_file()
+        if not font_path:
+            sys.exit("No TrueType font found; set FONT_PATH or TIME_FONT")
+        try:
+            print_glyph_metrics(font_path, positive_int(size), text)
+        except (argparse.ArgumentTypeError, OSError) as e:
+            sys.exit(f"--metrics: {e}")
+        return
+    
     # Setup logging
     log_level = os.environ.get('LOG_LEVEL', 'INFO')
     setup_logging(log_level)
@@ -1946,79 +4057,58 @@ def main():
     logging.info("=" * 60)
     
     # Load configuration
-    CONFIG_PATH = Path(__file__).parent / "config.yaml"
+    CONFIG_PATH = args.config
     SETTINGS_PATH = Path("/data/settings.yaml")
     
     try:
-        # Load base configuration
-        with open(CONFIG_PATH, 'r') as f:
-            config = yaml.safe_load(f)
-        logging.info("Base configuration loaded from config.yaml")
-        
-        # Override with settings from UI if available
-        if SETTINGS_PATH.exists():
-            with open(SETTINGS_PATH, 'r') as f:
-                ui_settings = yaml.safe_load(f) or {}
-            logging.info(f"Loaded {len(ui_settings)} settings from settings UI")
-            
-            # Map UI setting names to config structure
-            # UI uses flat keys like WEATHER_ENABLED, config uses nested structure
-            if 'WEATHER_ENABLED' in ui_settings:
-                config['weather']['enabled'] = ui_settings['WEATHER_ENABLED']
-            if 'WEATHER_LOCATION' in ui_settings:
-                config['weather']['location'] = ui_settings['WEATHER_LOCATION']
-            if 'WEATHER_API_KEY' in ui_settings:
-                config['weather']['api_key'] = ui_settings['WEATHER_API_KEY']
-            if 'TIMEZONE' in ui_settings:
-                config.setdefault('time', {})
-                config['time']['timezone'] = ui_settings['TIMEZONE']
-            if 'DISPLAY_COLOR' in ui_settings:
-                config['display']['color'] = ui_settings['DISPLAY_COLOR']
-            if 'TIME_FORMAT' in ui_settings:
-                # '12' or '24' from UI -> boolean in config
-                config.setdefault('time', {})
-                config['time']['format_12h'] = (str(ui_settings['TIME_FORMAT']) == '12')
-            # DISPLAY_DATE not supported explicitly; leave for future
-            if 'SHIFT_ENABLED' in ui_settings:
-                config.setdefault('display', {})
-                config['display']['pixel_shift_enabled'] = bool(ui_settings['SHIFT_ENABLED'])
-            if 'SHIFT_INTERVAL' in ui_settings:
-                config.setdefault('display', {})
-                try:
-                    config['display']['pixel_shift_interval_seconds'] = int(ui_settings['SHIFT_INTERVAL'])
-                except Exception:
-                    pass
-            # SHIFT_RANGE not present in config; ignore
-            if 'SCREENSAVER_ENABLED' in ui_settings:
-                config.setdefault('display', {})
-                config['display']['screensaver_enabled'] = bool(ui_settings['SCREENSAVER_ENABLED'])
-            if 'SCREENSAVER_START' in ui_settings:
-                try:
-                    start_hour = int(str(ui_settings['SCREENSAVER_START']).split(':')[0])
-                    config.setdefault('display', {})
-                    config['display']['screensaver_start_hour'] = start_hour
-                except Exception:
-                    pass
-            if 'SCREENSAVER_END' in ui_settings:
-                try:
-                    end_hour = int(str(ui_settings['SCREENSAVER_END']).split(':')[0])
-                    config.setdefault('display', {})
-                    config['display']['screensaver_end_hour'] = end_hour
-                except Exception:
-                    pass
-                
+        config = load_config(CONFIG_PATH, SETTINGS_PATH)
         logging.info("Configuration loaded successfully")
     except Exception as e:
-        logging.error(f"Error loading configuration: {e}")
-        sys.exit(1)
+        logging.error(f"Error loading configuration from {CONFIG_PATH}: {e}")
+        sys.exit(EXIT_CONFIG_ERROR)
     
     # Load build info and log comprehensive runtime summary
     build_info = load_build_info()
     log_runtime_summary(config, build_info)
     
     # Create and run clock
-    clock = FramebufferClock(config, build_info=build_info)
+    try:
+        clock = FramebufferClock(config, build_info=build_info)
+    except Exception as e:
+        logging.error(f"Failed to initialize clock: {e}", exc_info=log_level.upper() == 'DEBUG')
+        sys.exit(EXIT_INIT_ERROR)
+    if args.benchmark is not None:
+        try:
+            run_benchmark(clock, args.benchmark)
+        finally:
+            clock.cleanup()
+        return
     clock.run()
+    
+    # SIGHUP: re-read the config files; on any error keep running with the previous configuration.
+    # The old clock keeps its last frame on screen until the new config has loaded.
+    while clock.reload_requested:
+        try:
+            new_config = load_config(CONFIG_PATH, SETTINGS_PATH)
+        except Exception as e:
+            logging.error(f"Reload failed ({e}), keeping the current configuration")
+            clock.reload_requested = False
+            clock.running = True
+            clock.run()
+            continue
+        clock.cleanup(blank=False)
+        try:
+            clock = FramebufferClock(new_config, build_info=build_info)
+            config = new_config
+            logging.info("Configuration reloaded")
+        except Exception as e:
+            logging.error(f"Reload failed ({e}), keeping the current configuration")
+            try:
+                clock = FramebufferClock(config, build_info=build_info)
+            except Exception as e:
+                logging.error(f"Failed to initialize clock: {e}", exc_info=log_level.upper() == 'DEBUG')
+                sys.exit(EXIT_INIT_ERROR)
+        clock.run()
 
 
 if __name__ == '__main__':
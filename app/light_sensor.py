@@ -0,0 +1,111 @@
+"""
+BH1750 Ambient Light Sensor - Optional auto-brightness from room lux.
+Reads the sensor on a background thread over raw I2C (no smbus dependency) so
+rendering never waits on the bus; read errors keep the last good value.
+"""
+
+import fcntl
+import logging
+import os
+import threading
+from typing import List, Optional, Tuple
+
+I2C_SLAVE = 0x0703  # ioctl: set the slave address for subsequent read()/write()
+
+BH1750_POWER_ON = 0x01
+BH1750_CONTINUOUS_HIGH_RES = 0x10  # 1 lx resolution, ~120 ms per measurement
+
+DEFAULT_CURVE = "0:0.1,10:0.3,100:0.7,400:1.0"
+
+
+def parse_curve(value: str) -> List[Tuple[float, float]]:
+    """Parse "lux:brightness,..." into points sorted by lux; raises ValueError if empty/malformed."""
+    points = []
+    for item in str(value).replace(' ', '').split(','):
+        if not item:
+            continue
+        lux, brightness = item.split(':')
+        points.append((float(lux), max(0.0, min(1.0, float(brightness)))))
+    if not points:
+        raise ValueError(f"no lux:brightness points in '{value}'")
+    return sorted(points)
+
+
+def brightness_for_lux(curve: List[Tuple[float, float]], lux: float) -> float:
+    """Linearly interpolate brightness for lux, clamping to the curve's end points."""
+    if lux <= curve[0][0]:
+        return curve[0][1]
+    for (lux0, b0), (lux1, b1) in zip(curve, curve[1:]):
+        if lux <= lux1:
+            return b0 + (b1 - b0) * (lux - lux0) / max(1e-9, lux1 - lux0)
+    return curve[-1][1]
+
+
+class LightSensor:
+    """BH1750 lux sensor sampled every `interval` seconds on a daemon thread."""
+
+    def __init__(self, bus: str = '/dev/i2c-1', address: int = 0x23, interval: float = 5.0):
+        """
+        Open the sensor and start sampling.
+
+        Args:
+            bus: I2C bus device (e.g. /dev/i2c-1)
+            address: BH1750 address (0x23, or 0x5C with ADDR pulled high)
+            interval: Seconds between samples
+
+        Raises:
+            OSError: If the bus can't be opened or the sensor doesn't answer
+        """
+        self.bus = bus
+        self.address = address
+        self.interval = interval
+        self.lux: Optional[float] = None  # Last good reading
+        self._fd = os.open(bus, os.O_RDWR)
+        try:
+            fcntl.ioctl(self._fd, I2C_SLAVE, address)
+            os.write(self._fd, bytes([BH1750_POWER_ON]))
+            os.write(self._fd, bytes([BH1750_CONTINUOUS_HIGH_RES]))
+        except OSError:
+            os.close(self._fd)
+            raise
+        logging.info(f"BH1750 light sensor on {bus} at 0x{address:02X}")
+        self._stop = threading.Event()
+        self._thread = threading.Thread(target=self._sample_loop, name='light-sensor', daemon=True)
+        self._thread.start()
+
+    def read_lux(self) -> float:
+        """Read the latest continuous-mode measurement in lux."""
+        data = os.read(self._fd, 2)
+        if len(data) != 2:
+            raise OSError(f"short read ({len(data)} bytes)")
+        return ((data[0] << 8) | data[1]) / 1.2
+
+    def close(self):
+        """Stop the sampling thread; it releases the bus itself once it exits (safe to call more than once).
+        The fd is never closed under a read still in flight, where a reused fd number could be read instead.
+        """
+        self._stop.set()
+        if self._thread is threading.current_thread():
+            return
+        self._thread.join(timeout=1.0)
+        if self._thread.is_alive():
+            logging.warning("Light sensor read still blocked; the bus is released when it returns")
+
+    def _sample_loop(self):
+        failing = False
+        try:
+            while not self._stop.is_set():
+                try:
+                    self.lux = self.read_lux()
+                    if failing:
+                        logging.info("Light sensor readings recovered")
+                    failing = False
+                except OSError as e:
+                    # Keep the last good value; only log the first failure of a streak
+                    if not failing:
+                        logging.warning(f"Light sensor read failed ({e}), keeping last value")
+                    failing = True
+                self._stop.wait(self.interval)
+        finally:
+            os.close(self._fd)
+            self._fd = None
@@ -0,0 +1,445 @@
+"""
+Unit tests for the framebuffer clock.
+Run from the repo root with: python3 -m unittest discover tests
+(needs the same numpy/Pillow/PyYAML packages as the clock itself).
+"""
+
+import os
+import sys
+import tempfile
+import unittest
+from datetime import datetime, timedelta
+from unittest import mock
+
+import numpy as np
+from PIL import Image
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), '..', 'app'))
+
+import framebuffer_clock  # noqa: E402
+from framebuffer_clock import FramebufferClock, EXIT_CONFIG_ERROR, kelvin_to_rgb  # noqa: E402
+
+
+class ClockTestCase(unittest.TestCase):
+    """Builds real clocks on a temp file standing in for the framebuffer device."""
+
+    def make_clock(self, width=320, height=240, fb_format='RGB565', config=None, env=None, fill=0):
+        """A FramebufferClock constructed through the fb_device/fb_size seam.
+        A plain file answers no ioctls, so FB_FORMAT picks the pixel format; env adds overrides.
+        """
+        bytes_per_pixel = 2 if fb_format == 'RGB565' else 4
+        fd, path = tempfile.mkstemp(prefix='fb-')
+        with os.fdopen(fd, 'wb') as f:
+            f.write(bytes([fill]) * (width * height * bytes_per_pixel))
+        self.addCleanup(os.remove, path)
+        with mock.patch.dict(os.environ, dict(env or {}, FB_FORMAT=fb_format)):
+            clock = FramebufferClock(config or {}, fb_device=path, fb_size=(width, height))
+        self.addCleanup(close_mapping, clock)
+        return clock
+
+
+def close_mapping(clock: FramebufferClock):
+    """Release the clock's framebuffer mapping (tests may have swapped in a bytearray)."""
+    for handle in (getattr(clock, 'fb_mmap', None), getattr(clock, '_fb_file', None)):
+        if hasattr(handle, 'close'):
+            handle.close()
+
+
+def pad_rows(clock: FramebufferClock, stride: int) -> FramebufferClock:
+    """Map the clock onto a bytearray with stride-byte rows (a plain file reports no line_length)."""
+    close_mapping(clock)
+    clock._fb_stride_bytes = stride
+    clock.fb_mmap = bytearray(stride * clock.fb_height)
+    clock.fb_shadow[:] = np.arange(clock.fb_width * clock.fb_height).reshape(clock.fb_height, clock.fb_width) + 1
+    return clock
+
+
+def mmap_pixels(clock: FramebufferClock) -> np.ndarray:
+    """The visible (height, width) pixels of the fake mmap, skipping row padding."""
+    rows = np.frombuffer(bytes(clock.fb_mmap), dtype='<u2').reshape(clock.fb_height, -1)
+    return rows[:, :clock.fb_width]
+
+
+class PixelFormatTest(ClockTestCase):
+    """Colors pack into each supported native format, with any alpha bits opaque."""
+
+    # Native words for pure red, green and blue
+    EXPECTED = {
+        'RGB565': [0xF800, 0x07E0, 0x001F],
+        'XRGB8888': [0x00FF0000, 0x0000FF00, 0x000000FF],
+        'ARGB8888': [0xFFFF0000, 0xFF00FF00, 0xFF0000FF],
+        'RGBA8888': [0xFF0000FF, 0x00FF00FF, 0x0000FFFF],
+    }
+
+    def test_pack_rgb(self):
+        rgb = np.array([[(255, 0, 0), (0, 255, 0), (0, 0, 255)]], dtype=np.uint8)
+        for fb_format, expected in self.EXPECTED.items():
+            clock = self.make_clock(4, 2, fb_format)
+            self.assertEqual(clock._pack_rgb(rgb)[0].tolist(), expected, fb_format)
+
+    def test_rgb565_sprites_to_native(self):
+        # Sprites are pre-converted to RGB565; full-intensity channels survive the expansion exactly
+        rgb565 = np.array([[0xF800, 0x07E0, 0x001F]], dtype=np.uint16)
+        for fb_format, expected in self.EXPECTED.items():
+            clock = self.make_clock(4, 2, fb_format)
+            self.assertEqual(clock._rgb565_to_native(rgb565)[0].tolist(), expected, fb_format)
+
+    def test_shadow_starts_opaque_black(self):
+        for fb_format, alpha in (('XRGB8888', 0), ('ARGB8888', 0xFF000000), ('RGBA8888', 0xFF)):
+            clock = self.make_clock(4, 2, fb_format)
+            self.assertTrue((clock.fb_shadow == alpha).all(), fb_format)
+
+
+class SnapshotTest(ClockTestCase):
+    """write_snapshot saves the shadow buffer as a binary PPM."""
+
+    def test_ppm_header_and_pixels(self):
+        clock = self.make_clock(4, 2, 'XRGB8888')
+        clock.fb_shadow[:] = 0x00FF8000
+        tmp_dir = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp_dir.cleanup)
+        path = os.path.join(tmp_dir.name, 'snap.ppm')
+        clock.write_snapshot(path)
+        with open(path, 'rb') as f:
+            data = f.read()
+        header = b'P6\n4 2\n255\n'
+        self.assertTrue(data.startswith(header))
+        self.assertEqual(data[len(header):], bytes([255, 128, 0]) * 8)
+
+
+class BorderTest(ClockTestCase):
+    """BORDER_TIME/BORDER_DATE parse and draw as bands inside the element's rect."""
+
+    def test_parse(self):
+        clock = self.make_clock(env={'BORDER_TIME': '3,#FF0000', 'BORDER_DATE': '0,#FFFFFF'})
+        self.assertEqual(clock.border_time, (3, (255, 0, 0)))
+        self.assertIsNone(clock.border_date)
+
+    def test_outline_stays_inside_rect(self):
+        clock = self.make_clock(8, 6)
+        clock._draw_rect_outline((1, 1, 6, 4), 1, (255, 255, 255))
+        drawn = (clock.fb_shadow == 0xFFFF).astype(int).tolist()
+        self.assertEqual(drawn, [
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [0, 1, 1, 1, 1, 1, 1, 0],
+            [0, 1, 0, 0, 0, 0, 1, 0],
+            [0, 1, 0, 0, 0, 0, 1, 0],
+            [0, 1, 1, 1, 1, 1, 1, 0],
+            [0, 0, 0, 0, 0, 0, 0, 0],
+        ])
+
+
+class PaddedStrideTest(ClockTestCase):
+    """Writes land at stride offsets and never touch the padding at the end of each row."""
+
+    WIDTH, HEIGHT, STRIDE = 6, 4, 16  # 12 visible bytes per 16-byte row
+
+    def padding(self, clock):
+        rows = np.frombuffer(bytes(clock.fb_mmap), dtype=np.uint8).reshape(self.HEIGHT, self.STRIDE)
+        return rows[:, self.WIDTH * 2:]
+
+    def test_full_redraw(self):
+        clock = pad_rows(self.make_clock(self.WIDTH, self.HEIGHT), self.STRIDE)
+        clock.write_to_framebuffer(None)
+        np.testing.assert_array_equal(mmap_pixels(clock), clock.fb_shadow)
+        self.assertFalse(self.padding(clock).any())
+        self.assertFalse(clock._full_redraw)
+
+
+    def test_dirty_rect(self):
+        clock = pad_rows(self.make_clock(self.WIDTH, self.HEIGHT), self.STRIDE)
+        clock._full_redraw = False
+        clock._dirty_rects = [(2, 1, 3, 2)]
+        clock.write_to_framebuffer(None)
+        expected = np.zeros_like(clock.fb_shadow)
+        expected[1:3, 2:5] = clock.fb_shadow[1:3, 2:5]
+        np.testing.assert_array_equal(mmap_pixels(clock), expected)
+        self.assertFalse(self.padding(clock).any())
+        self.assertEqual(clock._dirty_rects, [])
+
+
+class BytesWrittenTest(ClockTestCase):
+    """fb_bytes_written counts visible pixel bytes (not row padding) per write."""
+
+    def test_counts_dirty_rects_and_full_redraws(self):
+        clock = pad_rows(self.make_clock(6, 4), 16)
+        clock.write_to_framebuffer(None)  # First frame: full redraw
+        self.assertEqual(clock.fb_bytes_written, 6 * 4 * 2)
+        clock._dirty_rects = [(0, 0, 2, 2), (3, 1, 3, 3)]
+        clock.write_to_framebuffer(None)
+        self.assertEqual(clock.fb_bytes_written, (6 * 4 + 2 * 2 + 3 * 3) * 2)
+
+    def test_clips_rects_to_screen(self):
+        clock = pad_rows(self.make_clock(6, 4), 16)
+        clock._full_redraw = False
+        clock._dirty_rects = [(4, 2, 10, 10), (1, 1, 0, 3)]
+        clock.write_to_framebuffer(None)
+        self.assertEqual(clock.fb_bytes_written, 2 * 2 * 2)
+
+
+class WriteErrorTest(ClockTestCase):
+    """A failed dirty-rect write drops the pending rects and queues a full redraw."""
+
+    def test_oserror_collapses_dirty_rects(self):
+        clock = self.make_clock(6, 4)
+        clock._unmap_framebuffer()
+        clock.fb_device += '.missing'
+        clock._full_redraw = False
+        clock._dirty_rects = [(0, 0, 2, 2), (3, 1, 3, 3)]
+        clock.write_to_framebuffer(None)
+        self.assertEqual(clock._dirty_rects, [])
+        self.assertTrue(clock._full_redraw)
+
+
+class CleanupTest(ClockTestCase):
+    """cleanup() blanks the whole framebuffer before unmapping it."""
+
+    def test_zeroes_framebuffer(self):
+        clock = self.make_clock(6, 4, fill=0xFF)
+        clock.cleanup()
+        with open(clock.fb_device, 'rb') as f:
+            self.assertEqual(f.read(), bytes(6 * 4 * 2))
+
+
+class AlignmentTest(ClockTestCase):
+    """CLOCK_ALIGN parses into one of nine placements, each pinned to the right edges."""
+
+    WIDTH, HEIGHT, MARGIN = 800, 480, 30
+    BLOCK_W, ABOVE, BELOW = 200, 60, 40  # Block extent around its anchor
+
+    def test_parse_alignment(self):
+        self.assertEqual(FramebufferClock.parse_alignment('left top'), ('left', 'top'))
+        self.assertEqual(FramebufferClock.parse_alignment('Bottom, RIGHT'), ('right', 'bottom'))
+        self.assertEqual(FramebufferClock.parse_alignment('top'), ('center', 'top'))
+        self.assertEqual(FramebufferClock.parse_alignment(''), ('center', 'middle'))
+        with self.assertLogs(level='WARNING'):
+            self.assertEqual(FramebufferClock.parse_alignment('upper right'), ('right', 'middle'))
+
+    def test_nine_combinations(self):
+        expected_x = {'left': self.MARGIN, 'center': (self.WIDTH - self.BLOCK_W) // 2,
+                      'right': self.WIDTH - self.MARGIN - self.BLOCK_W}
+        expected_top = {'top': self.MARGIN, 'middle': self.HEIGHT // 2 - self.ABOVE,
+                        'bottom': self.HEIGHT - self.MARGIN - self.ABOVE - self.BELOW}
+        for horizontal in ('left', 'center', 'right'):
+            for vertical in ('top', 'middle', 'bottom'):
+                clock = self.make_clock(self.WIDTH, self.HEIGHT, env={'CLOCK_ALIGN': f"{horizontal} {vertical}"})
+                x = clock._aligned_x(self.BLOCK_W, self.WIDTH // 2, self.MARGIN)
+                center_y = clock._aligned_center_y(self.HEIGHT // 2, self.ABOVE, self.BELOW, self.MARGIN)
+                self.assertEqual((x, center_y - self.ABOVE), (expected_x[horizontal], expected_top[vertical]),
+                                 f"{horizontal} {vertical}")
+
+    def test_pixel_shift_moves_pinned_edges_inwards(self):
+        clock = self.make_clock(self.WIDTH, self.HEIGHT, config={'display': {'align': 'right top'}})
+        self.assertEqual(clock._aligned_x(self.BLOCK_W, self.WIDTH // 2 - 5, self.MARGIN),
+                         self.WIDTH - self.MARGIN - self.BLOCK_W - 5)
+        self.assertEqual(clock._aligned_center_y(self.HEIGHT // 2 + 5, self.ABOVE, self.BELOW, self.MARGIN),
+                         self.MARGIN + self.ABOVE + 5)
+
+
+class TabularDigitsTest(ClockTestCase):
+    """_pad_digit_sprites gives every digit the same advance, centered in its slot."""
+
+    def test_prerendered_digit_widths_equal(self):
+        clock = self.make_clock(env={'TABULAR_DIGITS': 'true'})
+        widths = {clock._sprite_cache[d]['width'] for d in '0123456789'}
+        self.assertEqual(len(widths), 1, widths)
+
+    def test_digits_centered_in_slot(self):
+        cache = {}
+        for i, digit in enumerate('0123456789'):
+            width, height = 4 + i % 4, 6
+            cache[digit] = {
+                'image': Image.new('RGB', (width, height), (255, 255, 255)),
+                'rgb565': np.full((height, width), 0xFFFF, dtype=np.uint16),
+                'alpha': np.full((height, width), 255, dtype=np.uint8),
+                'width': width,
+                'height': height,
+            }
+        clock = self.make_clock()
+        clock._sprite_cache = cache
+        clock._pad_digit_sprites()
+        for digit, info in cache.items():
+            self.assertEqual(info['width'], 7, digit)
+            self.assertEqual(info['rgb565'].shape, (6, 7), digit)
+            self.assertEqual(info['alpha'].shape, (6, 7), digit)
+            self.assertEqual(info['image'].size, (7, 6), digit)
+        # A 4px digit gets 1px on the left and 2px on the right
+        self.assertEqual(list(cache['0']['rgb565'][0]), [0, 0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF, 0, 0])
+
+
+class SettingsTest(ClockTestCase):
+    """Bad numeric settings warn naming the variable or key and fall back; out-of-range ones clamp."""
+
+    def warnings_for(self, config=None, env=None):
+        with self.assertLogs(level='WARNING') as logs:
+            clock = self.make_clock(config=config, env=env)
+        return clock, '\n'.join(logs.output)
+
+    def test_bad_env_value(self):
+        clock, output = self.warnings_for(env={'TIME_FONT_SIZE': 'huge'})
+        self.assertEqual(clock.base_time_font_size, 280)
+        self.assertIn("TIME_FONT_SIZE='huge'", output)
+
+    def test_bad_env_value_falls_back_to_config(self):
+        clock, _ = self.warnings_for(config={'display': {'time_font_size': 200}}, env={'TIME_FONT_SIZE': 'huge'})
+        self.assertEqual(clock.base_time_font_size, 200)
+
+    def test_bad_config_value(self):
+        clock, output = self.warnings_for(config={'display': {'date_font_size': 'big'}})
+        self.assertEqual(clock.base_date_font_size, 90)
+        self.assertIn('config date_font_size', output)
+
+    def test_clamped(self):
+        clock, output = self.warnings_for(config={'display': {'time_font_size': 2}}, env={'DATE_FONT_SIZE': '900'})
+        self.assertEqual(clock.base_time_font_size, 10)
+        self.assertEqual(clock.base_date_font_size, 500)
+        self.assertIn('config time_font_size=2 out of range', output)
+        self.assertIn('DATE_FONT_SIZE=900 out of range', output)
+
+    def test_bad_color(self):
+        clock, output = self.warnings_for(env={'DISPLAY_COLOR': '#12345'})
+        self.assertEqual(clock.color, (0, 255, 0))
+        self.assertIn('DISPLAY_COLOR', output)
+
+    def test_single_hash_only(self):
+        clock, output = self.warnings_for(env={'DISPLAY_COLOR': '#FF0000', 'DATE_COLOR': '##FF0000'})
+        self.assertEqual(clock.color, (255, 0, 0))
+        self.assertEqual(clock.date_color, (255, 0, 0))  # Falls back to the main color
+        self.assertIn('DATE_COLOR', output)
+
+
+class ConfigFileTest(unittest.TestCase):
+    """A missing or non-mapping config file exits with EXIT_CONFIG_ERROR."""
+
+    def exit_code(self, config_path):
+        with mock.patch.object(sys, 'argv', ['framebuffer_clock.py', '--config', config_path]):
+            with self.assertRaises(SystemExit) as raised:
+                framebuffer_clock.main()
+        return raised.exception.code
+
+    def test_missing_config(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            self.assertEqual(self.exit_code(os.path.join(tmp, 'config.yaml')), EXIT_CONFIG_ERROR)
+
+    def test_non_mapping_config(self):
+        for text in ('- just\n- a list\n', '', 'display: [\n'):
+            with tempfile.NamedTemporaryFile('w', suffix='.yaml', delete=False) as f:
+                f.write(text)
+            self.addCleanup(os.remove, f.name)
+            self.assertEqual(self.exit_code(f.name), EXIT_CONFIG_ERROR, repr(text))
+
+
+class RotationTest(ClockTestCase):
+    """_to_physical maps logical pixels to the physically rotated framebuffer."""
+
+    WIDTH, HEIGHT = 5, 3  # Logical canvas
+
+    def expected(self, rotation, x, y):
+        """Physical (x, y) of logical (x, y) for a clockwise rotation."""
+        w, h = self.WIDTH, self.HEIGHT
+        return {
+            0: (x, y),
+            90: (h - 1 - y, x),
+            180: (w - 1 - x, h - 1 - y),
+            270: (y, w - 1 - x),
+        }[rotation]
+
+    def test_corners_and_blocks(self):
+        for rotation in (0, 90, 180, 270):
+            phys_w, phys_h = (self.HEIGHT, self.WIDTH) if rotation in (90, 270) else (self.WIDTH, self.HEIGHT)
+            clock = self.make_clock(phys_w, phys_h, env={'ROTATION': str(rotation)})
+            self.assertEqual((clock.fb_width, clock.fb_height), (self.WIDTH, self.HEIGHT), rotation)
+            # Place a 2x3 block of distinct values at each logical corner
+            for bx, by in ((0, 0), (self.WIDTH - 3, 0), (0, self.HEIGHT - 2), (self.WIDTH - 3, self.HEIGHT - 2)):
+                block = np.arange(1, 7, dtype=np.uint16).reshape(2, 3)
+                physical = np.zeros((phys_h, phys_w), dtype=np.uint16)
+                out, px, py = clock._to_physical(block, bx, by)
+                physical[py:py + out.shape[0], px:px + out.shape[1]] = out
+                for dy in range(2):
+                    for dx in range(3):
+                        ex, ey = self.expected(rotation, bx + dx, by + dy)
+                        self.assertEqual(physical[ey, ex], block[dy, dx],
+                                         f"rotation={rotation} logical=({bx + dx},{by + dy})")
+
+    def test_portrait_orientation_maps_to_90(self):
+        clock = self.make_clock()
+        with mock.patch.dict(os.environ):
+            for key in ('ROTATION', 'DISPLAY_ORIENTATION'):
+                os.environ.pop(key, None)
+            self.assertEqual(clock.get_rotation({'orientation': 'portrait', 'rotation': 0}), 90)
+            self.assertEqual(clock.get_rotation({'orientation': 'portrait', 'rotation': 270}), 270)
+            self.assertEqual(clock.get_rotation({'orientation': 'landscape'}), 0)
+            os.environ['ROTATION'] = '180'
+            self.assertEqual(clock.get_rotation({'orientation': 'portrait'}), 180)
+
+
+class LayoutTest(ClockTestCase):
+    """_place_time_date stacks time above date line_gap apart, centered on the screen."""
+
+    def place(self, height, config=None, env=None):
+        """Top/bottom rows of the time and date canvases on a 16:10 screen of the given height."""
+        width = height * 16 // 10
+        clock = self.make_clock(width, height, config={'display': dict(config or {}, line_gap=40)}, env=env)
+        # Canvas heights proportional to the screen, as with auto-sized fonts; a hidden line has none
+        time_size = (width // 2, height * 3 // 10) if clock.show_time else (0, 0)
+        date_size = (width // 3, height // 12) if clock.show_date else (0, 0)
+        _, (_, time_top), (_, date_top) = clock._place_time_date(time_size, date_size, width // 2, width // 2,
+                                                                 height // 2, 30)
+        return time_top, time_top + time_size[1], date_top, date_top + date_size[1]
+
+    def test_stacked_and_centered(self):
+        for height in (480, 720, 1200):
+            time_top, time_bottom, date_top, date_bottom = self.place(height)
+            self.assertEqual(date_top - time_bottom, 40, height)
+            # Equal space above and below the block (within a pixel of rounding)
+            self.assertLessEqual(abs(time_top - (height - date_bottom)), 1, height)
+            self.assertGreaterEqual(time_top, 30, height)
+            self.assertLessEqual(date_bottom, height - 30, height)
+
+    def test_lone_line_centers_on_its_own(self):
+        for height in (480, 720, 1200):
+            time_top, time_bottom, _, _ = self.place(height, config={'show_date': False})
+            self.assertLessEqual(abs(time_top - (height - time_bottom)), 1, height)
+
+    def test_fixed_offsets_scale(self):
+        clock = self.make_clock(1600, 1000, config={'display': {'time_offset_y': 60, 'date_offset_y': 100}},
+                                env={'DISPLAY_RESOLUTION': '800x500'})
+        self.assertEqual(clock._stack_offsets(200, 50), (30, 50))
+
+    def test_pinned_position_clamped_on_screen(self):
+        clock = self.make_clock(800, 480, env={'TIME_POS': '40,60', 'DATE_POS': '700,-5'})
+        _, time_pos, date_pos = clock._place_time_date((300, 120), (200, 40), 400, 400, 240, 30)
+        self.assertEqual(time_pos, (40, 60))  # inside the margin band, left alone
+        self.assertEqual(date_pos, (600, 0))
+
+
+class CountdownTest(ClockTestCase):
+    """COUNTDOWN_TO replaces the time with the remaining time, then flashes for the alarm window."""
+
+    def test_remaining_time_and_alarm(self):
+        clock = self.make_clock(env={'COUNTDOWN_TO': '2026-01-01T12:00:00', 'TIME_FORMAT_12H': 'false',
+                                     'SHOW_SECONDS': 'false'})
+        self.assertEqual(clock.format_time(datetime(2026, 1, 1, 11, 34, 30)), '25:30')
+        self.assertEqual(clock.format_time(datetime(2026, 1, 1, 9, 59, 59, 500000)), '2:00:01')
+        self.assertEqual(clock.format_time(datetime(2026, 1, 1, 12, 0, 30)), '00:00')
+        self.assertEqual(clock._countdown_phase(datetime(2026, 1, 1, 12, 0, 30)), 'alarm')
+        self.assertEqual(clock.format_time(datetime(2026, 1, 1, 12, 1, 0)), '12:01')
+
+    def test_time_of_day_targets_next_occurrence(self):
+        target = FramebufferClock._parse_countdown('00:00')
+        self.assertGreater(target, datetime.now())
+        self.assertLessEqual(target - datetime.now(), timedelta(days=1))
+        self.assertIsNone(FramebufferClock._parse_countdown('25 minutes'))
+
+
+class KelvinTest(unittest.TestCase):
+    """kelvin_to_rgb follows the Tanner Helland blackbody fit."""
+
+    def test_reference_temperatures(self):
+        self.assertEqual(kelvin_to_rgb(1900), (255, 132, 0))
+        self.assertEqual(kelvin_to_rgb(2700), (255, 167, 87))
+        self.assertEqual(kelvin_to_rgb(6500), (255, 254, 250))
+
+
+if __name__ == '__main__':
+    unittest.main()